@@ -0,0 +1,98 @@
+//! Extractor for actix-web apps which match routes with their own [`matchit::Router`] in a
+//! middleware.
+
+use crate::{ParamsBuf, ParamsDeserializationError};
+use actix_web::{
+    FromRequest, HttpMessage, HttpRequest, ResponseError, dev::Payload, http::StatusCode,
+};
+use core::future::{Ready, ready};
+use serde::de::DeserializeOwned;
+
+/// Extracts `T` from the [`ParamsBuf`] the middleware stored in the request extensions.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use actix_web::HttpMessage;
+/// use matchit_serde::{ParamsBuf, actix::MatchitPath};
+///
+/// #[derive(Deserialize)]
+/// struct UserPost {
+///     user: String,
+///     post: u32,
+/// }
+///
+/// async fn handler(MatchitPath(params): MatchitPath<UserPost>) -> String {
+///     format!("post {} by {}", params.post, params.user)
+/// }
+///
+/// // in the middleware
+/// # let request = actix_web::test::TestRequest::default().to_http_request();
+/// let mut router = matchit::Router::new();
+/// router.insert("/users/{user}/posts/{post}", ()).unwrap();
+/// let matched = router.at("/users/alice/posts/42").unwrap();
+/// request
+///     .extensions_mut()
+///     .insert(ParamsBuf::try_from(&matched.params).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchitPath<T>(pub T);
+
+impl<T> FromRequest for MatchitPath<T>
+where
+    T: DeserializeOwned,
+{
+    type Error = ParamsDeserializationError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<ParamsBuf>()
+                .ok_or(ParamsDeserializationError::MissingParams)
+                .and_then(ParamsBuf::deserialize)
+                .map(Self),
+        )
+    }
+}
+
+/// Responds with `500 Internal Server Error` to
+/// [server errors](ParamsDeserializationError::is_server_error) and with `400 Bad Request`
+/// otherwise.
+impl ResponseError for ParamsDeserializationError {
+    fn status_code(&self) -> StatusCode {
+        if self.is_server_error() {
+            StatusCode::INTERNAL_SERVER_ERROR
+        } else {
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MatchitPath;
+    use crate::{ParamsBuf, ParamsDeserializationError};
+    use actix_web::{
+        FromRequest, HttpMessage, ResponseError, dev::Payload, http::StatusCode, test::TestRequest,
+    };
+
+    #[test]
+    fn test_error_response() {
+        let req = TestRequest::default().to_http_request();
+        let err = MatchitPath::<u32>::from_request(&req, &mut Payload::None)
+            .into_inner()
+            .unwrap_err();
+        assert!(matches!(err, ParamsDeserializationError::MissingParams));
+        assert_eq!(
+            err.error_response().status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        req.extensions_mut()
+            .insert(ParamsBuf::from(vec![("id".to_owned(), "x".to_owned())]));
+        let err = MatchitPath::<u32>::from_request(&req, &mut Payload::None)
+            .into_inner()
+            .unwrap_err();
+        assert_eq!(err.error_response().status(), StatusCode::BAD_REQUEST);
+    }
+}
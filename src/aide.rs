@@ -0,0 +1,53 @@
+//! aide support: [`MatchitPath`] documents its params as path parameters, like aide does for
+//! axum's `Path<T>`.
+
+use crate::axum::MatchitPath;
+use ::aide::{
+    OperationInput,
+    generate::GenContext,
+    openapi::Operation,
+    operation::{ParamLocation, add_parameters, parameters_from_schema},
+};
+use schemars_0_9::JsonSchema;
+
+impl<T: JsonSchema> OperationInput for MatchitPath<T> {
+    fn operation_input(ctx: &mut GenContext, operation: &mut Operation) {
+        let schema = ctx.schema.subschema_for::<T>();
+        let params = parameters_from_schema(ctx, schema, ParamLocation::Path);
+        add_parameters(ctx, operation, params);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::aide::{generate::in_context, openapi::ReferenceOr};
+    use serde::Deserialize;
+
+    #[allow(dead_code)]
+    #[derive(Deserialize, JsonSchema)]
+    #[schemars(crate = "schemars_0_9")]
+    struct RepoParams {
+        owner: String,
+        #[serde(rename = "repo-id")]
+        repo_id: u64,
+    }
+
+    #[test]
+    fn test_operation_input() {
+        let mut operation = Operation::default();
+        in_context(|ctx| MatchitPath::<RepoParams>::operation_input(ctx, &mut operation));
+        let parameters = operation
+            .parameters
+            .iter()
+            .map(|parameter| match parameter {
+                ReferenceOr::Item(parameter) => {
+                    let data = parameter.parameter_data_ref();
+                    (data.name.as_str(), data.required)
+                }
+                _ => unreachable!("parameters are inlined"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(parameters, [("owner", true), ("repo-id", true)]);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::ParamsBuf;
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use alloc::{string::String, vec::Vec};
+
+const KEY_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789_";
+
+fn key(u: &mut Unstructured<'_>) -> Result<String> {
+    let len = u.int_in_range(1..=16)?;
+    (0..len)
+        .map(|_| u.choose(KEY_CHARS).map(|c| char::from(*c)))
+        .collect()
+}
+
+/// Generates params with unique keys that are valid in matchit templates and arbitrary values.
+impl<'a> Arbitrary<'a> for ParamsBuf {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let len = u.arbitrary_len::<(String, String)>()?;
+        let mut params: Vec<(String, String)> = Vec::with_capacity(len);
+        for _ in 0..len {
+            let key = key(u)?;
+            let value = String::arbitrary(u)?;
+            if params.iter().all(|(k, _)| *k != key) {
+                params.push((key, value));
+            }
+        }
+        Ok(params.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arbitrary_params_buf() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&bytes);
+        let params = ParamsBuf::arbitrary(&mut u).unwrap();
+        let params = params.as_params();
+        for (idx, (key, _)) in params.iter().enumerate() {
+            assert!(!key.is_empty());
+            assert!(key.bytes().all(|c| KEY_CHARS.contains(&c)));
+            assert!(params.iter().skip(idx + 1).all(|(k, _)| k != key));
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Extractor for axum apps which match routes with their own [`matchit::Router`], for example
+//! inside a fallback service or a tower middleware.
+
+use crate::{ExtensionParams, ParamsDeserializationError};
+use axum_core::{
+    extract::FromRequestParts,
+    response::{IntoResponse, Response},
+};
+use http::{StatusCode, request::Parts};
+use serde::de::DeserializeOwned;
+
+/// Extracts `T` from the [`ParamsBuf`](crate::ParamsBuf) the router stored in the request
+/// extensions, like axum's `Path<T>` does for its own router.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::{ParamsBuf, axum::MatchitPath};
+///
+/// #[derive(Deserialize)]
+/// struct UserPost {
+///     user: String,
+///     post: u32,
+/// }
+///
+/// async fn handler(MatchitPath(params): MatchitPath<UserPost>) -> String {
+///     format!("post {} by {}", params.post, params.user)
+/// }
+///
+/// // in the service matching the request
+/// let mut router = matchit::Router::new();
+/// router.insert("/users/{user}/posts/{post}", ()).unwrap();
+/// let matched = router.at("/users/alice/posts/42").unwrap();
+/// let mut request = http::Request::new(());
+/// request
+///     .extensions_mut()
+///     .insert(ParamsBuf::try_from(&matched.params).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchitPath<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for MatchitPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = MatchitPathRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let ExtensionParams(params) = ExtensionParams::from_extensions(&parts.extensions)?;
+        Ok(Self(params))
+    }
+}
+
+/// Rejection of [`MatchitPath`].
+///
+/// Responds with `500 Internal Server Error` to
+/// [server errors](ParamsDeserializationError::is_server_error) and with `400 Bad Request`
+/// otherwise.
+#[derive(Debug, Clone)]
+pub struct MatchitPathRejection(pub ParamsDeserializationError);
+
+impl MatchitPathRejection {
+    /// Returns the status code of the response.
+    pub fn status(&self) -> StatusCode {
+        if self.0.is_server_error() {
+            StatusCode::INTERNAL_SERVER_ERROR
+        } else {
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+impl From<ParamsDeserializationError> for MatchitPathRejection {
+    fn from(err: ParamsDeserializationError) -> Self {
+        Self(err)
+    }
+}
+
+impl core::fmt::Display for MatchitPathRejection {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl core::error::Error for MatchitPathRejection {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl IntoResponse for MatchitPathRejection {
+    fn into_response(self) -> Response {
+        (self.status(), self.0.to_string()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MatchitPath, MatchitPathRejection};
+    use crate::{ParamsBuf, ParamsDeserializationError, test_util::poll_once};
+    use axum_core::{extract::FromRequestParts, response::IntoResponse};
+    use core::error::Error;
+    use http::{Request, StatusCode, header::CONTENT_TYPE};
+
+    #[test]
+    fn test_rejection() {
+        let (mut parts, ()) = Request::new(()).into_parts();
+        parts
+            .extensions
+            .insert(ParamsBuf::from(vec![("id".to_owned(), "x".to_owned())]));
+        let rejection =
+            poll_once(MatchitPath::<u32>::from_request_parts(&mut parts, &())).unwrap_err();
+        assert!(matches!(
+            rejection.source().unwrap().downcast_ref(),
+            Some(ParamsDeserializationError::ParseError { .. })
+        ));
+
+        let response = rejection.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            "text/plain; charset=utf-8"
+        );
+    }
+
+    #[test]
+    fn test_rejection_status() {
+        let rejection = MatchitPathRejection::from(ParamsDeserializationError::MissingParams);
+        assert_eq!(rejection.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,305 @@
+//! Base58 and base62 encoded params, like the IDs of short links.
+//!
+//! [`BaseX`] decodes a param with the alphabet `A` into an unsigned integer or into bytes. Other
+//! alphabets can be used by implementing [`Alphabet`].
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt, iter,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+use serde::{de, ser};
+
+/// The digits of a base, in order of their value.
+pub trait Alphabet {
+    /// The ASCII digits, at most 256 of them.
+    const ALPHABET: &'static [u8];
+}
+
+/// The Bitcoin base58 alphabet, without `0`, `O`, `I` and `l`.
+pub struct Base58;
+
+impl Alphabet for Base58 {
+    const ALPHABET: &'static [u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+}
+
+/// The base62 alphabet of digits, uppercase and lowercase letters.
+pub struct Base62;
+
+impl Alphabet for Base62 {
+    const ALPHABET: &'static [u8] =
+        b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+}
+
+/// A value decoded with the alphabet `A`.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::base_x::{Base58, Base62, BaseX};
+///
+/// #[derive(Deserialize)]
+/// struct Link {
+///     id: BaseX<u64, Base62>,
+///     key: BaseX<Vec<u8>, Base58>,
+/// }
+///
+/// let link: Link = matchit_serde::from_slice(&[("id", "G8"), ("key", "1Ldp")]).unwrap();
+/// assert_eq!(*link.id, 1000);
+/// assert_eq!(*link.key, [0, 1, 2, 3]);
+/// ```
+pub struct BaseX<T, A>(pub T, PhantomData<A>);
+
+impl<T, A> BaseX<T, A> {
+    /// Wraps `value`, for example to serialize it with the alphabet of `A`.
+    pub fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Returns the decoded value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Values which can be converted from and to digits of a base.
+///
+/// Integers are plain numbers in the base. Bytes keep their leading zeros as leading zero digits,
+/// like Bitcoin's base58.
+pub trait BaseXValue: Sized {
+    /// Builds the value from the digit values, most significant first. Fails on overflow.
+    fn from_digits(digits: &[u8], base: u32) -> Option<Self>;
+
+    /// Returns the digit values, most significant first.
+    fn to_digits(&self, base: u32) -> Vec<u8>;
+}
+
+macro_rules! integer_value {
+    ($($ty:ty)*) => {
+        $(
+            impl BaseXValue for $ty {
+                fn from_digits(digits: &[u8], base: u32) -> Option<Self> {
+                    digits.iter().try_fold(0 as $ty, |value, &digit| {
+                        value
+                            .checked_mul(base as $ty)?
+                            .checked_add(digit as $ty)
+                    })
+                }
+
+                fn to_digits(&self, base: u32) -> Vec<u8> {
+                    let mut value = *self;
+                    let mut digits = Vec::new();
+                    loop {
+                        digits.push((value % base as $ty) as u8);
+                        value /= base as $ty;
+                        if value == 0 {
+                            break;
+                        }
+                    }
+                    digits.reverse();
+                    digits
+                }
+            }
+        )*
+    };
+}
+
+integer_value!(u32 u64 u128);
+
+/// Converts between big-endian numbers with the digits `from` and `to`, keeping leading zeros.
+fn convert(digits: &[u8], from: u32, to: u32) -> Vec<u8> {
+    let zeros = digits.iter().take_while(|&&digit| digit == 0).count();
+    // Little-endian while converting.
+    let mut converted: Vec<u8> = Vec::new();
+    for &digit in &digits[zeros..] {
+        let mut carry = u32::from(digit);
+        for converted in &mut converted {
+            carry += u32::from(*converted) * from;
+            *converted = (carry % to) as u8;
+            carry /= to;
+        }
+        while carry > 0 {
+            converted.push((carry % to) as u8);
+            carry /= to;
+        }
+    }
+    converted.extend(iter::repeat_n(0, zeros));
+    converted.reverse();
+    converted
+}
+
+impl BaseXValue for Vec<u8> {
+    fn from_digits(digits: &[u8], base: u32) -> Option<Self> {
+        Some(convert(digits, base, 256))
+    }
+
+    fn to_digits(&self, base: u32) -> Vec<u8> {
+        convert(self, 256, base)
+    }
+}
+
+impl<const N: usize> BaseXValue for [u8; N] {
+    fn from_digits(digits: &[u8], base: u32) -> Option<Self> {
+        convert(digits, base, 256).try_into().ok()
+    }
+
+    fn to_digits(&self, base: u32) -> Vec<u8> {
+        convert(self, 256, base)
+    }
+}
+
+impl<'de, T, A> de::Deserialize<'de> for BaseX<T, A>
+where
+    T: BaseXValue,
+    A: Alphabet,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BaseXVisitor(PhantomData))
+    }
+}
+
+struct BaseXVisitor<T, A>(PhantomData<(T, A)>);
+
+impl<T, A> de::Visitor<'_> for BaseXVisitor<T, A>
+where
+    T: BaseXValue,
+    A: Alphabet,
+{
+    type Value = BaseX<T, A>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a base{} value", A::ALPHABET.len())
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let digits = value
+            .chars()
+            .map(|character| {
+                A::ALPHABET
+                    .iter()
+                    .position(|&digit| char::from(digit) == character)
+                    .map(|digit| digit as u8)
+                    .ok_or_else(|| {
+                        E::invalid_value(
+                            de::Unexpected::Char(character),
+                            &"a digit of the alphabet",
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if digits.is_empty() {
+            return Err(E::invalid_value(de::Unexpected::Str(value), &self));
+        }
+        T::from_digits(&digits, A::ALPHABET.len() as u32)
+            .map(BaseX::new)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+impl<T, A> ser::Serialize for BaseX<T, A>
+where
+    T: BaseXValue,
+    A: Alphabet,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let value: String = self
+            .0
+            .to_digits(A::ALPHABET.len() as u32)
+            .into_iter()
+            .map(|digit| char::from(A::ALPHABET[usize::from(digit)]))
+            .collect();
+        serializer.serialize_str(&value)
+    }
+}
+
+impl<T, A> Deref for BaseX<T, A> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, A> DerefMut for BaseX<T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Debug, A> fmt::Debug for BaseX<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BaseX").field(&self.0).finish()
+    }
+}
+
+impl<T: Clone, A> Clone for BaseX<T, A> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<T: PartialEq, A> PartialEq for BaseX<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq, A> Eq for BaseX<T, A> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Base58, Base62, BaseX};
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use alloc::vec::Vec;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Link {
+        id: BaseX<u64, Base62>,
+        key: BaseX<Vec<u8>, Base58>,
+        hash: BaseX<[u8; 4], Base58>,
+    }
+
+    #[test]
+    fn test_base_x() {
+        let link: Link =
+            from_slice(&[("id", "LygHa16AHYF"), ("key", "11Ldp"), ("hash", "6h8cQN")]).unwrap();
+        assert_eq!(*link.id, u64::MAX);
+        assert_eq!(*link.key, [0, 0, 1, 2, 3]);
+        assert_eq!(*link.hash, [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(
+            to_pairs(&link).unwrap(),
+            [("id", "LygHa16AHYF"), ("key", "11Ldp"), ("hash", "6h8cQN")]
+                .map(|(k, v)| (k.to_owned(), v.to_owned()))
+        );
+
+        let err = from_slice::<Link>(&[("id", "LygHa16AHYG"), ("key", "1"), ("hash", "6h8cQN")])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "id"
+        ));
+
+        let err =
+            from_slice::<Link>(&[("id", "0"), ("key", "10"), ("hash", "6h8cQN")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::InvalidCharacter { character: '0', .. }
+        ));
+
+        let err = from_slice::<Link>(&[("id", "0"), ("key", "1"), ("hash", "Ldp")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "hash"
+        ));
+    }
+}
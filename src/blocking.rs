@@ -0,0 +1,113 @@
+//! Helpers for synchronous servers such as tiny_http or rouille.
+
+use crate::ParamsBuf;
+use alloc::string::{String, ToString};
+use serde::de::DeserializeOwned;
+
+/// Matches `path` against `router` and deserializes the params of the matched route into `T`.
+///
+/// Returns the value of the matched route along with the params, or a [`Rejection`] which can be
+/// sent as response right away.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::blocking::route;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct User {
+///     id: u32,
+/// }
+///
+/// let mut router = matchit::Router::new();
+/// router.insert("/users/{id}", "user").unwrap();
+///
+/// let (name, user) = route::<_, User>(&router, "/users/42").unwrap();
+/// assert_eq!((*name, user.id), ("user", 42));
+///
+/// let rejection = route::<_, User>(&router, "/users/x").unwrap_err();
+/// assert_eq!(rejection.status(), 400);
+/// // with rouille:
+/// // rouille::Response::text(rejection.message()).with_status_code(rejection.status())
+/// ```
+pub fn route<'r, V, T>(router: &'r matchit::Router<V>, path: &str) -> Result<(&'r V, T), Rejection>
+where
+    T: DeserializeOwned,
+{
+    let matched = router.at(path).map_err(|_| Rejection {
+        status: 404,
+        message: String::from("Not Found"),
+    })?;
+    let params = ParamsBuf::try_from(&matched.params)
+        .and_then(|params| params.with_raw_path(path).deserialize())
+        .map_err(|err| Rejection {
+            status: 400,
+            message: err.to_string(),
+        })?;
+    Ok((matched.value, params))
+}
+
+/// A `404 Not Found` or `400 Bad Request` response for a request which couldn't be routed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    status: u16,
+    message: String,
+}
+
+impl Rejection {
+    /// Returns the status code of the response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the plain text body of the response.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl core::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}", self.status, self.message)
+    }
+}
+
+impl core::error::Error for Rejection {}
+
+#[cfg(feature = "tiny_http")]
+impl From<Rejection> for tiny_http::Response<std::io::Cursor<alloc::vec::Vec<u8>>> {
+    fn from(rejection: Rejection) -> Self {
+        tiny_http::Response::from_string(rejection.message).with_status_code(rejection.status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::route;
+
+    #[test]
+    fn test_route() {
+        let mut router = matchit::Router::new();
+        router.insert("/users/{id}", 1).unwrap();
+
+        assert_eq!(route::<_, (u32,)>(&router, "/users/7").unwrap(), (&1, (7,)));
+
+        let rejection = route::<_, (u32,)>(&router, "/posts/7").unwrap_err();
+        assert_eq!(
+            (rejection.status(), rejection.message()),
+            (404, "Not Found")
+        );
+
+        let rejection = route::<_, (u32,)>(&router, "/users/%FF").unwrap_err();
+        assert_eq!(rejection.status(), 400);
+    }
+
+    #[cfg(feature = "tiny_http")]
+    #[test]
+    fn test_tiny_http_response() {
+        let mut router = matchit::Router::new();
+        router.insert("/users/{id}", ()).unwrap();
+        let rejection = route::<_, (u32,)>(&router, "/users/x").unwrap_err();
+        let response = tiny_http::Response::from(rejection);
+        assert_eq!(response.status_code(), 400);
+    }
+}
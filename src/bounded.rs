@@ -0,0 +1,231 @@
+use alloc::string::String;
+use core::{fmt, marker::PhantomData, ops::Deref, str::FromStr};
+use serde::{de, ser};
+
+/// An integer param within `MIN..=MAX`.
+///
+/// A value out of bounds fails like a value which can't be parsed, with the bounds in the
+/// expected type.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::{Bounded, ParamsDeserializationError};
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Listing {
+///     page: Bounded<u32, 1, 1000>,
+/// }
+///
+/// let listing: Listing = matchit_serde::from_slice(&[("page", "3")]).unwrap();
+/// assert_eq!(*listing.page, 3);
+///
+/// let err = matchit_serde::from_slice::<Listing>(&[("page", "0")]).unwrap_err();
+/// assert!(matches!(
+///     err,
+///     ParamsDeserializationError::ParseErrorAtKey { expected_type, .. }
+///         if expected_type.ends_with("Bounded<u32, 1, 1000>")
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<T, const MIN: i128, const MAX: i128>(T);
+
+impl<T, const MIN: i128, const MAX: i128> Bounded<T, MIN, MAX>
+where
+    T: Copy,
+    i128: TryFrom<T>,
+{
+    /// Wraps `value` if it is within bounds.
+    pub fn new(value: T) -> Option<Self> {
+        i128::try_from(value)
+            .is_ok_and(|value| (MIN..=MAX).contains(&value))
+            .then_some(Self(value))
+    }
+}
+
+impl<T, const MIN: i128, const MAX: i128> Bounded<T, MIN, MAX> {
+    /// Returns the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const MIN: i128, const MAX: i128> Deref for Bounded<T, MIN, MAX> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'de, T, const MIN: i128, const MAX: i128> de::Deserialize<'de> for Bounded<T, MIN, MAX>
+where
+    T: FromStr + Copy,
+    i128: TryFrom<T>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BoundedVisitor(PhantomData))
+    }
+}
+
+struct BoundedVisitor<T, const MIN: i128, const MAX: i128>(PhantomData<T>);
+
+impl<T, const MIN: i128, const MAX: i128> de::Visitor<'_> for BoundedVisitor<T, MIN, MAX>
+where
+    T: FromStr + Copy,
+    i128: TryFrom<T>,
+{
+    type Value = Bounded<T, MIN, MAX>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an integer between {MIN} and {MAX}")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value
+            .parse()
+            .ok()
+            .and_then(Bounded::new)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+impl<T: ser::Serialize, const MIN: i128, const MAX: i128> ser::Serialize for Bounded<T, MIN, MAX> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+/// A string param which isn't empty.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::NonEmptyString;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Search {
+///     term: NonEmptyString,
+/// }
+///
+/// let search: Search = matchit_serde::from_slice(&[("term", "rust")]).unwrap();
+/// assert_eq!(&*search.term, "rust");
+/// assert!(matchit_serde::from_slice::<Search>(&[("term", "")]).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonEmptyString(String);
+
+impl NonEmptyString {
+    /// Wraps `value` if it isn't empty.
+    pub fn new(value: String) -> Option<Self> {
+        (!value.is_empty()).then_some(Self(value))
+    }
+
+    /// Returns the string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for NonEmptyString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for NonEmptyString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for NonEmptyString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(NonEmptyStringVisitor)
+    }
+}
+
+struct NonEmptyStringVisitor;
+
+impl de::Visitor<'_> for NonEmptyStringVisitor {
+    type Value = NonEmptyString;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a non-empty string")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        NonEmptyString::new(value.into())
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+impl ser::Serialize for NonEmptyString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Listing {
+        page: Bounded<u32, 1, 100>,
+        offset: Bounded<i64, -10, 10>,
+        term: NonEmptyString,
+    }
+
+    #[test]
+    fn test_bounds() {
+        let listing: Listing =
+            from_slice(&[("page", "100"), ("offset", "-10"), ("term", "a")]).unwrap();
+        assert_eq!(listing.page.into_inner(), 100);
+        assert_eq!(*listing.offset, -10);
+        assert_eq!(&*listing.term, "a");
+        assert_eq!(
+            to_pairs(&listing).unwrap(),
+            [("page", "100"), ("offset", "-10"), ("term", "a")]
+                .map(|(k, v)| (k.to_owned(), v.to_owned()))
+        );
+
+        for (page, offset, term, failing) in [
+            ("0", "0", "a", "page"),
+            ("101", "0", "a", "page"),
+            ("x", "0", "a", "page"),
+            ("1", "11", "a", "offset"),
+            ("1", "0", "", "term"),
+        ] {
+            let err = from_slice::<Listing>(&[("page", page), ("offset", offset), ("term", term)])
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == failing
+            ));
+        }
+
+        assert_eq!(Bounded::<u128, 0, 10>::new(u128::MAX), None);
+        assert_eq!(NonEmptyString::new(String::new()), None);
+    }
+}
@@ -0,0 +1,110 @@
+//! `#[serde(with)]` helpers for `bson` params.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use bson::oid::ObjectId;
+//!
+//! #[derive(Deserialize)]
+//! struct Order {
+//!     #[serde(with = "matchit_serde::bson::object_id")]
+//!     id: ObjectId,
+//! }
+//!
+//! let order: Order =
+//!     matchit_serde::from_slice(&[("id", "65f1c0ffee0123456789abcd")]).unwrap();
+//! assert_eq!(order.id.to_hex(), "65f1c0ffee0123456789abcd");
+//! ```
+
+/// 24 character hex [`ObjectId`](::bson::oid::ObjectId)s.
+///
+/// Unlike `ObjectId`'s own impl, a value of the wrong length fails with
+/// [`InvalidLength`](crate::ParamsDeserializationError::InvalidLength) and a non-hex character
+/// with [`InvalidCharacter`](crate::ParamsDeserializationError::InvalidCharacter).
+pub mod object_id {
+    use ::bson::oid::ObjectId;
+    use core::fmt;
+    use serde::{Deserializer, Serializer, de};
+
+    struct ObjectIdVisitor;
+
+    impl de::Visitor<'_> for ObjectIdVisitor {
+        type Value = ObjectId;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("24 hex characters")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<ObjectId, E>
+        where
+            E: de::Error,
+        {
+            if let Some(character) = value.chars().find(|c| !c.is_ascii_hexdigit()) {
+                return Err(E::invalid_value(
+                    de::Unexpected::Char(character),
+                    &"a hex digit",
+                ));
+            }
+            if value.len() != 24 {
+                return Err(E::invalid_length(value.len(), &self));
+            }
+            ObjectId::parse_str(value)
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ObjectId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ObjectIdVisitor)
+    }
+
+    pub fn serialize<S>(value: &ObjectId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&value.to_hex())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use bson::oid::ObjectId;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Order {
+        #[serde(with = "super::object_id")]
+        id: ObjectId,
+    }
+
+    #[test]
+    fn test_object_ids() {
+        let hex = "65f1c0ffee0123456789abcd";
+        let order: Order = from_slice(&[("id", hex)]).unwrap();
+        assert_eq!(order.id, ObjectId::parse_str(hex).unwrap());
+        assert_eq!(
+            to_pairs(&order).unwrap(),
+            [("id".to_owned(), hex.to_owned())]
+        );
+
+        let err = from_slice::<Order>(&[("id", "65f1c0ffee")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::InvalidLength { got: 10, .. }
+        ));
+        assert_eq!(err.key(), Some("id"));
+
+        let err = from_slice::<Order>(&[("id", "65f1c0ffee0123456789abcg")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::InvalidCharacter { character: 'g', .. }
+        ));
+        assert_eq!(err.key(), Some("id"));
+        assert_eq!(
+            err.to_string(),
+            "Invalid character 'g' at key \"id\", expected a hex digit"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! `#[serde(with)]` helpers deserializing sizes like `10MB` or `512KiB` into a plain `u64` of
+//! bytes.
+//!
+//! [`ByteSize`] params deserialize without a helper.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Quota {
+//!     #[serde(with = "matchit_serde::bytesize")]
+//!     limit: u64,
+//! }
+//!
+//! let quota: Quota = matchit_serde::from_slice(&[("limit", "512KiB")]).unwrap();
+//! assert_eq!(quota.limit, 512 * 1024);
+//! ```
+
+use crate::parse::ParseVisitor;
+use ::bytesize::ByteSize;
+use core::marker::PhantomData;
+use serde::{Deserializer, Serializer};
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |value| value.parse::<ByteSize>().ok().map(|size| size.as_u64()),
+        expecting: "a size like `10MB` or `512KiB`",
+        marker: PhantomData,
+    })
+}
+
+/// Serializes the exact number of bytes, without a unit.
+pub fn serialize<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_u64(*value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use bytesize::ByteSize;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Quota {
+        #[serde(with = "super")]
+        limit: u64,
+        size: ByteSize,
+    }
+
+    #[test]
+    fn test_sizes() {
+        for (value, bytes) in [
+            ("10MB", 10_000_000),
+            ("512KiB", 524_288),
+            ("1%20GiB", 1 << 30),
+            ("42", 42),
+        ] {
+            let quota: Quota = from_slice(&[("limit", value), ("size", value)]).unwrap();
+            assert_eq!(quota.limit, bytes);
+            assert_eq!(quota.size, ByteSize::b(bytes));
+        }
+        let quota: Quota = from_slice(&[("limit", "1kb"), ("size", "1")]).unwrap();
+        assert_eq!(
+            to_pairs(&quota).unwrap()[0],
+            ("limit".to_owned(), "1000".to_owned())
+        );
+
+        let err = from_slice::<Quota>(&[("limit", "10 parsecs"), ("size", "1")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "limit"
+        ));
+        let err = from_slice::<Quota>(&[("limit", "1"), ("size", "MB")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                if key == "size" && expected_type.ends_with("ByteSize")
+        ));
+    }
+}
@@ -0,0 +1,105 @@
+//! [`Utf8PathBuf`](::camino::Utf8PathBuf) params, typically captured by a catch-all like
+//! `/files/{*path}`.
+//!
+//! `Utf8PathBuf` params deserialize without a helper and are taken as is. Use [`safe`] for paths
+//! that get joined to a base directory, so the param can't escape it.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use camino::Utf8PathBuf;
+//!
+//! #[derive(Deserialize)]
+//! struct File {
+//!     #[serde(with = "matchit_serde::camino::safe")]
+//!     path: Utf8PathBuf,
+//! }
+//!
+//! let file: File = matchit_serde::from_slice(&[("path", "docs/intro.md")]).unwrap();
+//! assert_eq!(file.path, "docs/intro.md");
+//! assert!(matchit_serde::from_slice::<File>(&[("path", "docs/../../etc/passwd")]).is_err());
+//! ```
+
+use ::camino::{Utf8Component, Utf8Path};
+
+/// Relative paths without `..` components, so joining them to a directory stays inside of it.
+///
+/// Absolute paths, Windows prefixes like `C:` and paths containing `\0` are rejected as well.
+pub mod safe {
+    use crate::parse::ParseVisitor;
+    use ::camino::{Utf8Path, Utf8PathBuf};
+    use core::marker::PhantomData;
+    use serde::{Deserializer, Serializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Utf8PathBuf, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ParseVisitor {
+            parse: |value| super::is_safe(Utf8Path::new(value)).then(|| value.into()),
+            expecting: "a relative path without `..` components",
+            marker: PhantomData,
+        })
+    }
+
+    pub fn serialize<S>(value: &Utf8Path, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(value.as_str())
+    }
+}
+
+fn is_safe(path: &Utf8Path) -> bool {
+    !path.as_str().contains('\0')
+        && path
+            .components()
+            .all(|component| matches!(component, Utf8Component::Normal(_) | Utf8Component::CurDir))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use camino::Utf8PathBuf;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct File {
+        #[serde(with = "super::safe")]
+        path: Utf8PathBuf,
+    }
+
+    #[test]
+    fn test_safe_paths() {
+        for value in [
+            "intro.md",
+            "docs/./intro.md",
+            "docs%2Fnested%2Fintro.md",
+            "ünïcode/ß",
+        ] {
+            let file: File = from_slice(&[("path", value)]).unwrap();
+            assert!(file.path.is_relative());
+        }
+        let file = File {
+            path: "docs/intro.md".into(),
+        };
+        assert_eq!(
+            to_pairs(&file).unwrap(),
+            [("path".to_owned(), "docs/intro.md".to_owned())]
+        );
+
+        for value in ["..", "docs/../../etc", "%2Fetc%2Fpasswd", "/etc", "a%00b"] {
+            let err = from_slice::<File>(&[("path", value)]).unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "path"
+            ));
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Any {
+            path: Utf8PathBuf,
+        }
+        let any: Any = from_slice(&[("path", "%2Fetc%2F..%2Fx")]).unwrap();
+        assert_eq!(any.path, "/etc/../x");
+    }
+}
@@ -0,0 +1,917 @@
+use crate::{Params, ParamsDeserializationError, ParamsDeserializer};
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::fmt;
+use serde::de;
+
+/// A lightweight check of a param value, enforced by [`Config`] before parsing.
+#[derive(Debug, Clone)]
+pub enum Constraint {
+    /// At most this many characters.
+    MaxLen(usize),
+    /// At least this many characters.
+    MinLen(usize),
+    /// One of these values.
+    OneOf(Vec<String>),
+    /// A number within the inclusive bounds.
+    Range { min: Option<f64>, max: Option<f64> },
+    /// Every character satisfies the predicate, like `|c| c.is_ascii_alphanumeric()`.
+    Chars(fn(char) -> bool),
+    /// The value matches the regex. Anchor it with `^` and `$` to match the whole value.
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl Constraint {
+    fn check(&self, value: &str) -> bool {
+        match self {
+            Self::MaxLen(max) => value.chars().count() <= *max,
+            Self::MinLen(min) => value.chars().count() >= *min,
+            Self::OneOf(values) => values.iter().any(|allowed| allowed == value),
+            Self::Range { min, max } => value.parse::<f64>().is_ok_and(|value| {
+                min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+            }),
+            Self::Chars(allowed) => value.chars().all(allowed),
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => regex.is_match(value),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::MaxLen(max) => format!("at most {max} characters"),
+            Self::MinLen(min) => format!("at least {min} characters"),
+            Self::OneOf(values) => format!("one of {}", values.join(", ")),
+            Self::Range {
+                min: Some(min),
+                max: Some(max),
+            } => format!("between {min} and {max}"),
+            Self::Range {
+                min: Some(min),
+                max: None,
+            } => format!("at least {min}"),
+            Self::Range {
+                min: None,
+                max: Some(max),
+            } => format!("at most {max}"),
+            Self::Range {
+                min: None,
+                max: None,
+            } => "a number".to_string(),
+            Self::Chars(_) => "allowed characters".to_string(),
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => format!("matching {regex}"),
+        }
+    }
+}
+
+/// Checks applied to [`Params`] before deserializing them.
+///
+/// A value violating a constraint is rejected with
+/// [`ParamsDeserializationError::ConstraintViolation`] without being parsed. Values longer than
+/// [`Config::DEFAULT_MAX_VALUE_LEN`] are rejected with
+/// [`ParamsDeserializationError::ValueTooLong`] unless configured otherwise. The limit only
+/// applies to params deserialized through a `Config`:
+/// [`ParamsDeserializer`](crate::ParamsDeserializer), [`from_params`](crate::from_params) and the
+/// framework adapters accept values of any length.
+///
+/// The number and combined size of the params can be capped too, which is worth doing when
+/// checking params merged from several [`Sources`](crate::Sources).
+///
+/// Prefixes like the `v` of `v2` can be stripped from values with [`Config::strip_prefix`], and
+/// sentinels like `latest` mapped with [`Config::none_if`] and [`Config::replace`]. Keys can be
+/// renamed systematically with [`Config::map_keys`].
+///
+/// Failures can be reported from one place with [`Config::on_error`].
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::{Config, Constraint, ParamsDeserializationError, params};
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Repo {
+///     owner: String,
+/// }
+///
+/// let config = Config::new()
+///     .constrain("owner", Constraint::MaxLen(39))
+///     .constrain("owner", Constraint::Chars(|c| c.is_ascii_alphanumeric() || c == '-'));
+///
+/// let repo: Repo = config.deserialize(params! { "owner" => "rust-lang" }).unwrap();
+/// assert_eq!(repo.owner, "rust-lang");
+///
+/// let err = config.deserialize::<Repo>(params! { "owner" => "../etc" }).unwrap_err();
+/// assert!(matches!(err, ParamsDeserializationError::ConstraintViolation { .. }));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Config {
+    constraints: Vec<(String, Constraint)>,
+    prefixes: Vec<(String, String)>,
+    sentinels: Vec<(String, String, Option<String>)>,
+    map_keys: Option<fn(&str) -> String>,
+    pairwise: Vec<String>,
+    max_value_len: usize,
+    max_params: usize,
+    max_total_len: usize,
+    on_error: Option<OnError>,
+}
+
+type ErrorHook = dyn Fn(&ParamsDeserializationError, &Params<'_>, Option<&str>) + Send + Sync;
+
+#[derive(Clone)]
+struct OnError(Arc<ErrorHook>);
+
+impl fmt::Debug for OnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnError").finish_non_exhaustive()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            constraints: Vec::new(),
+            prefixes: Vec::new(),
+            sentinels: Vec::new(),
+            map_keys: None,
+            pairwise: Vec::new(),
+            max_value_len: Self::DEFAULT_MAX_VALUE_LEN,
+            max_params: usize::MAX,
+            max_total_len: usize::MAX,
+            on_error: None,
+        }
+    }
+}
+
+impl Config {
+    /// The default maximum length of a value in bytes, 8 KiB. Only enforced by [`Config`].
+    pub const DEFAULT_MAX_VALUE_LEN: usize = 8 * 1024;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum length of a value in bytes. Use `usize::MAX` to disable the limit.
+    pub fn max_value_len(mut self, max: usize) -> Self {
+        self.max_value_len = max;
+        self
+    }
+
+    /// Sets the maximum number of params. Unlimited by default.
+    pub fn max_params(mut self, max: usize) -> Self {
+        self.max_params = max;
+        self
+    }
+
+    /// Sets the maximum combined length of all keys and values in bytes. Unlimited by default.
+    pub fn max_total_len(mut self, max: usize) -> Self {
+        self.max_total_len = max;
+        self
+    }
+
+    /// Sets a callback invoked with the error, the params and the route template, if known,
+    /// whenever [`deserialize`](Self::deserialize) or
+    /// [`deserialize_route`](Self::deserialize_route) fails, before the error is returned.
+    ///
+    /// ```
+    /// use matchit_serde::{Config, params};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static FAILURES: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// let config = Config::new().on_error(|err, params, template| {
+    ///     FAILURES.fetch_add(1, Ordering::Relaxed);
+    ///     eprintln!("{template:?}: {err} ({})", params.redacted(&["token"]));
+    /// });
+    /// assert!(config.deserialize_route::<u32>("/{id}", params! { "id" => "x" }).is_err());
+    /// assert_eq!(FAILURES.load(Ordering::Relaxed), 1);
+    /// ```
+    pub fn on_error(
+        mut self,
+        on_error: impl Fn(&ParamsDeserializationError, &Params<'_>, Option<&str>)
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.on_error = Some(OnError(Arc::new(on_error)));
+        self
+    }
+
+    /// Adds a constraint for the values of `key`. A key can have any number of constraints.
+    pub fn constrain(mut self, key: impl Into<String>, constraint: Constraint) -> Self {
+        self.constraints.push((key.into(), constraint));
+        self
+    }
+
+    /// Strips `prefix` from the values of `key` before they are checked and parsed, so numeric
+    /// targets work with prefixed IDs like `v2` or `#42`. Values without the prefix are kept as
+    /// they are.
+    ///
+    /// ```
+    /// use matchit_serde::{Config, params};
+    ///
+    /// let config = Config::new().strip_prefix("version", "v");
+    /// let version: u32 = config.deserialize(params! { "version" => "v123" }).unwrap();
+    /// assert_eq!(version, 123);
+    /// ```
+    pub fn strip_prefix(mut self, key: impl Into<String>, prefix: impl Into<String>) -> Self {
+        self.prefixes.push((key.into(), prefix.into()));
+        self
+    }
+
+    /// Drops the values of `key` equal to `sentinel`, so an `Option` field for the key is `None`.
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// use matchit_serde::{Config, params};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Release {
+    ///     version: Option<String>,
+    /// }
+    ///
+    /// let config = Config::new().none_if("version", "latest");
+    /// let release: Release = config.deserialize(params! { "version" => "latest" }).unwrap();
+    /// assert_eq!(release.version, None);
+    /// let release: Release = config.deserialize(params! { "version" => "1.2.3" }).unwrap();
+    /// assert_eq!(release.version.as_deref(), Some("1.2.3"));
+    /// ```
+    pub fn none_if(mut self, key: impl Into<String>, sentinel: impl Into<String>) -> Self {
+        self.sentinels.push((key.into(), sentinel.into(), None));
+        self
+    }
+
+    /// Replaces the values of `key` equal to `sentinel` with `replacement`, like `me` with the ID
+    /// of the current user.
+    pub fn replace(
+        mut self,
+        key: impl Into<String>,
+        sentinel: impl Into<String>,
+        replacement: impl Into<String>,
+    ) -> Self {
+        self.sentinels
+            .push((key.into(), sentinel.into(), Some(replacement.into())));
+        self
+    }
+
+    /// Maps every key with `map_keys` before anything else, so route naming conventions can
+    /// differ from the field names without renaming each field. Constraints, prefixes and
+    /// sentinels are registered for the mapped keys.
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// use matchit_serde::{Config, params};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Lookup {
+    ///     user_id: u32,
+    /// }
+    ///
+    /// let config = Config::new().map_keys(|key| key.replace('-', "_"));
+    /// let lookup: Lookup = config.deserialize(params! { "user-id" => "7" }).unwrap();
+    /// assert_eq!(lookup.user_id, 7);
+    /// ```
+    pub fn map_keys(mut self, map_keys: fn(&str) -> String) -> Self {
+        self.map_keys = Some(map_keys);
+        self
+    }
+
+    /// Expands the value of the catch-all `key` into params from alternating key and value
+    /// segments, like `region/eu/tier/gold` into `region=eu` and `tier=gold`, for label selectors
+    /// embedded in paths. The catch-all param itself is removed. Empty segments are skipped, and
+    /// an odd number of segments fails with [`ParamsDeserializationError::ConstraintViolation`].
+    ///
+    /// The value is split after percent-decoding, so an encoded `/` separates segments too.
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// use matchit_serde::{Config, params};
+    /// use std::collections::BTreeMap;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Selector {
+    ///     kind: String,
+    ///     #[serde(flatten)]
+    ///     labels: BTreeMap<String, String>,
+    /// }
+    ///
+    /// let config = Config::new().pairwise("labels");
+    /// let selector: Selector = config
+    ///     .deserialize(params! { "kind" => "pod", "labels" => "region/eu/tier/gold" })
+    ///     .unwrap();
+    /// assert_eq!(selector.labels["region"], "eu");
+    /// assert_eq!(selector.labels["tier"], "gold");
+    /// ```
+    pub fn pairwise(mut self, key: impl Into<String>) -> Self {
+        self.pairwise.push(key.into());
+        self
+    }
+
+    fn is_pairwise(&self, key: &str) -> bool {
+        let key = match self.map_keys {
+            Some(map_keys) => Cow::Owned(map_keys(key)),
+            None => Cow::Borrowed(key),
+        };
+        self.pairwise.iter().any(|pairwise| *pairwise == key)
+    }
+
+    /// Fails if a pairwise catch-all has an odd number of segments.
+    fn check_pairwise(&self, params: &Params<'_>) -> Result<(), ParamsDeserializationError> {
+        for (key, value) in params.iter() {
+            let segments = value.split('/').filter(|segment| !segment.is_empty());
+            if self.is_pairwise(key) && !segments.count().is_multiple_of(2) {
+                return Err(ParamsDeserializationError::ConstraintViolation {
+                    key: key.to_string(),
+                    constraint: "alternating keys and values".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn expand_pairwise<'de>(&self, params: Params<'de>) -> Params<'de> {
+        if self.pairwise.is_empty() {
+            return params;
+        }
+        let mut expanded = Vec::with_capacity(params.len());
+        for (key, value) in params.0 {
+            if !self.pairwise.iter().any(|pairwise| *pairwise == key) {
+                expanded.push((key, value));
+                continue;
+            }
+            let segments: Vec<Cow<'de, str>> = match value {
+                Cow::Borrowed(value) => value
+                    .split('/')
+                    .filter(|segment| !segment.is_empty())
+                    .map(Cow::Borrowed)
+                    .collect(),
+                Cow::Owned(value) => value
+                    .split('/')
+                    .filter(|segment| !segment.is_empty())
+                    .map(|segment| Cow::Owned(segment.to_string()))
+                    .collect(),
+            };
+            let mut segments = segments.into_iter();
+            while let (Some(key), Some(value)) = (segments.next(), segments.next()) {
+                let key = match self.map_keys {
+                    Some(map_keys) => Cow::Owned(map_keys(&key)),
+                    None => key,
+                };
+                expanded.push((key, value));
+            }
+        }
+        Params(expanded)
+    }
+
+    /// Maps the keys, expands the pairwise catch-alls, applies the sentinels and strips the
+    /// prefixes, in that order.
+    fn preprocess<'de>(&self, mut params: Params<'de>) -> Params<'de> {
+        if let Some(map_keys) = self.map_keys {
+            for (key, _) in &mut params.0 {
+                let mapped = map_keys(key);
+                if mapped != *key {
+                    *key = Cow::Owned(mapped);
+                }
+            }
+        }
+        let mut params = self.expand_pairwise(params);
+        if self.prefixes.is_empty() && self.sentinels.is_empty() {
+            return params;
+        }
+        params.0.retain_mut(|(key, value)| {
+            let sentinel = self
+                .sentinels
+                .iter()
+                .find(|(mapped, sentinel, _)| mapped == key && sentinel == value);
+            match sentinel {
+                Some((_, _, Some(replacement))) => {
+                    *value = Cow::Owned(replacement.clone());
+                    true
+                }
+                Some((_, _, None)) => false,
+                None => true,
+            }
+        });
+        for (key, value) in &mut params.0 {
+            let prefix = self
+                .prefixes
+                .iter()
+                .find(|(prefixed, prefix)| prefixed == key && value.starts_with(prefix.as_str()));
+            if let Some((_, prefix)) = prefix {
+                *value = match value {
+                    Cow::Borrowed(value) => Cow::Borrowed(&value[prefix.len()..]),
+                    Cow::Owned(value) => Cow::Owned(value[prefix.len()..].to_string()),
+                };
+            }
+        }
+        params
+    }
+
+    /// Checks `params` against the configured constraints.
+    pub fn check(&self, params: &Params<'_>) -> Result<(), ParamsDeserializationError> {
+        if params.len() > self.max_params {
+            return Err(ParamsDeserializationError::TooManyParams {
+                got: params.len(),
+                max: self.max_params,
+            });
+        }
+        let total_len = params
+            .iter()
+            .map(|(key, value)| key.len() + value.len())
+            .sum();
+        if total_len > self.max_total_len {
+            return Err(ParamsDeserializationError::ParamsTooLarge {
+                len: total_len,
+                max: self.max_total_len,
+            });
+        }
+        for (key, value) in params.iter() {
+            if value.len() > self.max_value_len {
+                return Err(ParamsDeserializationError::ValueTooLong {
+                    key: key.to_string(),
+                    len: value.len(),
+                    max: self.max_value_len,
+                });
+            }
+            let violated = self
+                .constraints
+                .iter()
+                .find(|(constrained, constraint)| constrained == key && !constraint.check(value));
+            if let Some((_, constraint)) = violated {
+                return Err(ParamsDeserializationError::ConstraintViolation {
+                    key: key.to_string(),
+                    constraint: constraint.describe(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks `params` and deserializes `T` from them.
+    pub fn deserialize<'de, T>(&self, params: Params<'de>) -> Result<T, ParamsDeserializationError>
+    where
+        T: de::Deserialize<'de>,
+    {
+        self.deserialize_at(None, params)
+    }
+
+    /// Like [`deserialize`](Self::deserialize), for the params of a route matching `template`.
+    pub fn deserialize_route<'de, T>(
+        &self,
+        template: &str,
+        params: Params<'de>,
+    ) -> Result<T, ParamsDeserializationError>
+    where
+        T: de::Deserialize<'de>,
+    {
+        self.deserialize_at(Some(template), params)
+    }
+
+    fn deserialize_at<'de, T>(
+        &self,
+        template: Option<&str>,
+        params: Params<'de>,
+    ) -> Result<T, ParamsDeserializationError>
+    where
+        T: de::Deserialize<'de>,
+    {
+        if let Err(err) = self.check_pairwise(&params) {
+            self.report(&err, &params, template);
+            return Err(err);
+        }
+        let params = self.preprocess(params);
+        if let Err(err) = self.check(&params) {
+            self.report(&err, &params, template);
+            return Err(err);
+        }
+        let deserializer = ParamsDeserializer::new(params);
+        let result = match template {
+            Some(template) => deserializer.deserialize_route(template),
+            None => deserializer.deserialize(),
+        };
+        if let Err(err) = &result {
+            self.report(err, &deserializer.0, template);
+        }
+        result
+    }
+
+    fn report(
+        &self,
+        err: &ParamsDeserializationError,
+        params: &Params<'_>,
+        template: Option<&str>,
+    ) {
+        if let Some(OnError(on_error)) = &self.on_error {
+            on_error(err, params, template);
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl Config {
+    /// Builds a config from the JSON schema of `T`, see [`Config::schema`].
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// use matchit_serde::{Config, ParamsDeserializationError, params};
+    /// use schemars::JsonSchema;
+    ///
+    /// #[derive(Debug, Deserialize, JsonSchema)]
+    /// struct Listing {
+    ///     #[schemars(range(min = 1, max = 100))]
+    ///     page: u32,
+    ///     #[schemars(regex(pattern = r"^[a-z]+$"))]
+    ///     tag: String,
+    /// }
+    ///
+    /// let config = Config::from_schema::<Listing>();
+    /// let listing: Listing = config.deserialize(params! { "page" => "2", "tag" => "rust" }).unwrap();
+    /// assert_eq!(listing.page, 2);
+    ///
+    /// let err = config
+    ///     .deserialize::<Listing>(params! { "page" => "200", "tag" => "rust" })
+    ///     .unwrap_err();
+    /// assert!(matches!(err, ParamsDeserializationError::ConstraintViolation { .. }));
+    /// ```
+    pub fn from_schema<T: schemars::JsonSchema>() -> Self {
+        Self::new().schema(&schemars::schema_for!(T))
+    }
+
+    /// Adds constraints for the properties of an object schema from the `enum`, `pattern`,
+    /// `minimum`, `maximum`, `minLength` and `maxLength` keywords. References to `$defs` and
+    /// optional properties are followed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a `pattern` isn't a valid regex.
+    pub fn schema(mut self, schema: &schemars::Schema) -> Self {
+        let root = schema.as_value();
+        let Some(properties) = root.get("properties").and_then(|p| p.as_object()) else {
+            return self;
+        };
+        for (key, property) in properties {
+            let property = crate::schema::resolve(root, property);
+            if let Some(values) = property.get("enum").and_then(|e| e.as_array()) {
+                let values = values
+                    .iter()
+                    .map(|value| match value {
+                        serde_json::Value::String(value) => value.clone(),
+                        value => value.to_string(),
+                    })
+                    .collect();
+                self = self.constrain(key, Constraint::OneOf(values));
+            }
+            if let Some(pattern) = property.get("pattern").and_then(|p| p.as_str()) {
+                let regex = regex::Regex::new(pattern)
+                    .unwrap_or_else(|err| panic!("Invalid pattern for key \"{key}\": {err}"));
+                self = self.constrain(key, Constraint::Regex(regex));
+            }
+            let min = property.get("minimum").and_then(|m| m.as_f64());
+            let max = property.get("maximum").and_then(|m| m.as_f64());
+            if min.is_some() || max.is_some() {
+                self = self.constrain(key, Constraint::Range { min, max });
+            }
+            if let Some(min) = property.get("minLength").and_then(|m| m.as_u64()) {
+                self = self.constrain(key, Constraint::MinLen(min as usize));
+            }
+            if let Some(max) = property.get("maxLength").and_then(|m| m.as_u64()) {
+                self = self.constrain(key, Constraint::MaxLen(max as usize));
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Item {
+        sku: String,
+        qty: u32,
+    }
+
+    #[test]
+    fn test_constraints() {
+        let config = Config::new()
+            .constrain("sku", Constraint::MaxLen(8))
+            .constrain(
+                "sku",
+                Constraint::Chars(|c| c.is_ascii_uppercase() || c.is_ascii_digit()),
+            )
+            .constrain("qty", Constraint::MaxLen(3));
+
+        let item: Item = config
+            .deserialize(params! { "sku" => "AB12", "qty" => "5" })
+            .unwrap();
+        assert_eq!(item.sku, "AB12");
+        assert_eq!(item.qty, 5);
+
+        for (sku, qty, key, constraint) in [
+            ("AB123456789", "5", "sku", "at most 8 characters"),
+            ("ab12", "5", "sku", "allowed characters"),
+            ("AB12", "99999999999", "qty", "at most 3 characters"),
+        ] {
+            let err = config
+                .deserialize::<Item>(params! { "sku" => sku, "qty" => qty })
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ConstraintViolation { key: ref k, constraint: ref c }
+                    if k == key && c == constraint
+            ));
+        }
+
+        let err = config
+            .deserialize::<Item>(params! { "sku" => "AB12", "qty" => "x" })
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { .. }
+        ));
+    }
+
+    #[test]
+    fn test_strip_prefix() {
+        let config = Config::new()
+            .strip_prefix("sku", "#")
+            .strip_prefix("qty", "x")
+            .constrain("sku", Constraint::MaxLen(4));
+
+        let item: Item = config
+            .deserialize(params! { "sku" => "#AB12", "qty" => "x5" })
+            .unwrap();
+        assert_eq!(item.sku, "AB12");
+        assert_eq!(item.qty, 5);
+
+        let item: Item = config
+            .deserialize(params! { "sku" => "AB12", "qty" => "5" })
+            .unwrap();
+        assert_eq!((item.sku.as_str(), item.qty), ("AB12", 5));
+
+        let params = Params::decode([("sku", "%23AB%31"), ("qty", "x5")].into_iter()).unwrap();
+        assert_eq!(config.deserialize::<Item>(params).unwrap().sku, "AB1");
+
+        let err = config
+            .deserialize::<Item>(params! { "sku" => "AB12", "qty" => "xx5" })
+            .unwrap_err();
+        assert_eq!(err.key(), Some("qty"));
+    }
+
+    #[test]
+    fn test_sentinels() {
+        #[derive(Debug, Deserialize)]
+        struct Release {
+            version: Option<String>,
+            user: u32,
+        }
+
+        let config = Config::new()
+            .none_if("version", "latest")
+            .replace("user", "me", "42")
+            .strip_prefix("version", "v");
+
+        let release: Release = config
+            .deserialize(params! { "version" => "latest", "user" => "me" })
+            .unwrap();
+        assert_eq!(release.version, None);
+        assert_eq!(release.user, 42);
+
+        let release: Release = config
+            .deserialize(params! { "version" => "v1.2.3", "user" => "7" })
+            .unwrap();
+        assert_eq!(release.version.as_deref(), Some("1.2.3"));
+        assert_eq!(release.user, 7);
+
+        let err = config
+            .deserialize::<Release>(params! { "version" => "latest", "user" => "you" })
+            .unwrap_err();
+        assert_eq!(err.key(), Some("user"));
+    }
+
+    #[test]
+    fn test_map_keys() {
+        let config = Config::new()
+            .map_keys(|key| key.strip_prefix("x-").unwrap_or(key).to_string())
+            .constrain("qty", Constraint::MaxLen(2));
+
+        let item: Item = config
+            .deserialize(params! { "x-sku" => "AB12", "qty" => "5" })
+            .unwrap();
+        assert_eq!((item.sku.as_str(), item.qty), ("AB12", 5));
+
+        let err = config
+            .deserialize::<Item>(params! { "sku" => "AB12", "x-qty" => "500" })
+            .unwrap_err();
+        assert_eq!(err.key(), Some("qty"));
+    }
+
+    #[test]
+    fn test_pairwise() {
+        #[derive(Debug, Deserialize)]
+        struct Selector {
+            kind: String,
+            region: String,
+            tier: Option<String>,
+        }
+
+        let config = Config::new().pairwise("labels");
+        let selector: Selector = config
+            .deserialize(params! { "kind" => "pod", "labels" => "region/eu/tier/gold/" })
+            .unwrap();
+        assert_eq!(selector.kind, "pod");
+        assert_eq!(selector.region, "eu");
+        assert_eq!(selector.tier.as_deref(), Some("gold"));
+
+        let selector: Selector = config
+            .deserialize(params! { "kind" => "pod", "labels" => "region/us" })
+            .unwrap();
+        assert_eq!((selector.region.as_str(), selector.tier), ("us", None));
+
+        let err = config
+            .deserialize::<Selector>(params! { "kind" => "pod", "labels" => "region/eu/tier" })
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ConstraintViolation { ref key, .. } if key == "labels"
+        ));
+    }
+
+    #[test]
+    fn test_max_value_len() {
+        let long = "A".repeat(Config::DEFAULT_MAX_VALUE_LEN + 1);
+        let err = Config::new()
+            .check(&params! { "sku" => long.as_str() })
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ValueTooLong { ref key, len, max: Config::DEFAULT_MAX_VALUE_LEN }
+                if key == "sku" && len == long.len()
+        ));
+
+        let config = Config::new().max_value_len(4);
+        assert!(config.check(&params! { "sku" => "AB12" }).is_ok());
+        assert!(config.check(&params! { "sku" => "AB123" }).is_err());
+        assert!(
+            Config::new()
+                .max_value_len(usize::MAX)
+                .check(&params! { "sku" => long.as_str() })
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_on_error() {
+        use std::sync::Mutex;
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let config = Config::new()
+            .constrain("sku", Constraint::MaxLen(4))
+            .on_error({
+                let reports = reports.clone();
+                move |err, params, template| {
+                    let report = (err.kind(), params.len(), template.map(String::from));
+                    reports.lock().unwrap().push(report);
+                }
+            });
+
+        let _ = config.deserialize::<Item>(params! { "sku" => "AB12", "qty" => "5" });
+        let _ = config.deserialize::<Item>(params! { "sku" => "AB12345" });
+        let _ = config
+            .deserialize_route::<Item>("/{sku}/{qty}", params! { "sku" => "AB", "qty" => "x" });
+        assert_eq!(
+            *reports.lock().unwrap(),
+            [
+                ("constraint_violation", 1, None),
+                ("parse_error_at_key", 2, Some("/{sku}/{qty}".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_params() {
+        let params = params! { "sku" => "AB12", "qty" => "5" };
+        assert!(Config::new().max_params(2).check(&params).is_ok());
+        assert!(matches!(
+            Config::new().max_params(1).check(&params),
+            Err(ParamsDeserializationError::TooManyParams { got: 2, max: 1 })
+        ));
+
+        assert!(Config::new().max_total_len(11).check(&params).is_ok());
+        assert!(matches!(
+            Config::new().max_total_len(10).check(&params),
+            Err(ParamsDeserializationError::ParamsTooLarge { len: 11, max: 10 })
+        ));
+    }
+
+    #[test]
+    fn test_value_constraints() {
+        let config = Config::new()
+            .constrain("sku", Constraint::MinLen(2))
+            .constrain("sku", Constraint::OneOf(vec!["AB".into(), "CD".into()]))
+            .constrain(
+                "qty",
+                Constraint::Range {
+                    min: Some(1.0),
+                    max: None,
+                },
+            )
+            .constrain(
+                "page",
+                Constraint::Range {
+                    min: None,
+                    max: None,
+                },
+            );
+        assert!(
+            config
+                .check(&params! { "sku" => "AB", "qty" => "3", "page" => "-1" })
+                .is_ok()
+        );
+
+        for (sku, qty, page, constraint) in [
+            ("A", "3", "1", "at least 2 characters"),
+            ("EF", "3", "1", "one of AB, CD"),
+            ("AB", "0", "1", "at least 1"),
+            ("AB", "x", "1", "at least 1"),
+            ("AB", "3", "x", "a number"),
+        ] {
+            let err = config
+                .check(&params! { "sku" => sku, "qty" => qty, "page" => page })
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ConstraintViolation { constraint: ref c, .. }
+                    if c == constraint
+            ));
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        #[derive(Debug, Deserialize, schemars::JsonSchema)]
+        #[serde(rename_all = "lowercase")]
+        enum State {
+            Open,
+            Closed,
+        }
+
+        #[derive(Debug, Deserialize, schemars::JsonSchema)]
+        struct Filter {
+            #[schemars(length(min = 2, max = 4))]
+            sku: String,
+            #[schemars(range(min = 1, max = 10))]
+            qty: Option<u32>,
+            state: Option<State>,
+        }
+
+        let config = Config::from_schema::<Filter>();
+        let filter: Filter = config
+            .deserialize(params! { "sku" => "AB", "qty" => "10", "state" => "open" })
+            .unwrap();
+        assert_eq!(filter.sku, "AB");
+        assert_eq!(filter.qty, Some(10));
+        assert!(matches!(filter.state, Some(State::Open)));
+
+        for (sku, qty, state, constraint) in [
+            ("A", "1", "open", "at least 2 characters"),
+            ("ABCDE", "1", "open", "at most 4 characters"),
+            ("AB", "11", "open", "between 1 and 10"),
+            ("AB", "1", "merged", "one of open, closed"),
+        ] {
+            let err = config
+                .deserialize::<Filter>(params! { "sku" => sku, "qty" => qty, "state" => state })
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ConstraintViolation { constraint: ref c, .. }
+                    if c == constraint
+            ));
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_constraint() {
+        let config = Config::new().constrain(
+            "sku",
+            Constraint::Regex(regex::Regex::new("^[A-Z]{2}[0-9]+$").unwrap()),
+        );
+        assert!(config.check(&params! { "sku" => "AB12" }).is_ok());
+        let err = config.check(&params! { "sku" => "A12" }).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ConstraintViolation { ref constraint, .. }
+                if constraint == "matching ^[A-Z]{2}[0-9]+$"
+        ));
+    }
+}
@@ -0,0 +1,105 @@
+//! `#[serde(with)]` helpers accepting a decimal comma in float params, like `3,14` for `3.14`,
+//! as sent by clients formatting numbers for some locales.
+//!
+//! A value with a single comma and no point has the comma read as the decimal separator. A point
+//! still works, but a comma used as a thousands separator, like `1,000.5`, fails to parse.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Point {
+//!     #[serde(with = "matchit_serde::decimal_comma")]
+//!     lat: f64,
+//!     #[serde(with = "matchit_serde::decimal_comma")]
+//!     lon: f64,
+//! }
+//!
+//! let point: Point = matchit_serde::from_slice(&[("lat", "52,52"), ("lon", "13.405")]).unwrap();
+//! assert_eq!((point.lat, point.lon), (52.52, 13.405));
+//! ```
+
+use crate::parse::ParseVisitor;
+use core::{any::type_name, fmt::Display, marker::PhantomData, str::FromStr};
+use serde::{Deserializer, Serializer};
+
+/// A float type the helpers can deserialize.
+pub trait Float: FromStr + Display {}
+
+impl Float for f32 {}
+impl Float for f64 {}
+
+fn parse<T: Float>(value: &str) -> Option<T> {
+    if value.contains('.') || value.matches(',').count() != 1 {
+        return value.parse().ok();
+    }
+    value.replace(',', ".").parse().ok()
+}
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Float,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: parse::<T>,
+        expecting: type_name::<T>(),
+        marker: PhantomData,
+    })
+}
+
+/// Serializes the float with a decimal point.
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Float,
+{
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Price {
+        #[serde(with = "super")]
+        amount: f64,
+        #[serde(with = "super")]
+        rate: f32,
+    }
+
+    #[test]
+    fn test_decimal_comma() {
+        for (amount, expected) in [
+            ("2,75", 2.75),
+            ("2%2C75", 2.75),
+            ("2.75", 2.75),
+            ("-0,5", -0.5),
+            (",5", 0.5),
+            ("42", 42.0),
+        ] {
+            let price: Price = from_slice(&[("amount", amount), ("rate", "1,5")]).unwrap();
+            assert_eq!(price.amount, expected);
+            assert_eq!(price.rate, 1.5);
+        }
+
+        for amount in ["1,000.5", "1,000,000", "2,75,", "x"] {
+            let err = from_slice::<Price>(&[("amount", amount), ("rate", "1")]).unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type: "f64", .. }
+                    if key == "amount"
+            ));
+        }
+
+        assert_eq!(
+            to_pairs(&Price {
+                amount: 2.75,
+                rate: 0.5
+            })
+            .unwrap(),
+            [("amount", "2.75"), ("rate", "0.5")].map(|(k, v)| (k.to_owned(), v.to_owned()))
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! `#[serde(with)]` helpers for [`Duration`] params given as plain numbers, like `30` or `1.5`.
+//!
+//! The submodule selects the unit of the number: [`secs`], [`millis`], [`micros`] or [`nanos`].
+//! Whole numbers are exact, fractional ones are rounded to the nanosecond. Negative, infinite
+//! and NaN values fail with a parse error at their key.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use std::time::Duration;
+//!
+//! #[derive(Deserialize)]
+//! struct Poll {
+//!     #[serde(with = "matchit_serde::duration::secs")]
+//!     timeout: Duration,
+//!     #[serde(with = "matchit_serde::duration::millis")]
+//!     interval: Duration,
+//! }
+//!
+//! let poll: Poll =
+//!     matchit_serde::from_slice(&[("timeout", "1.5"), ("interval", "250")]).unwrap();
+//! assert_eq!(poll.timeout, Duration::from_millis(1500));
+//! assert_eq!(poll.interval, Duration::from_millis(250));
+//! ```
+
+use alloc::string::{String, ToString};
+use core::time::Duration;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+fn parse(value: &str, per_sec: u64) -> Option<Duration> {
+    if let Ok(value) = value.parse::<u64>() {
+        let nanos = (value % per_sec) * (NANOS_PER_SEC / per_sec);
+        return Some(Duration::new(value / per_sec, nanos as u32));
+    }
+    let value = value.parse::<f64>().ok()?;
+    Duration::try_from_secs_f64(value / per_sec as f64).ok()
+}
+
+/// Writes the duration as a whole number of units if it is one, and as a fraction otherwise.
+fn format(value: &Duration, per_sec: u64) -> String {
+    let nanos_per_unit = u128::from(NANOS_PER_SEC / per_sec);
+    let nanos = value.as_nanos();
+    if nanos.is_multiple_of(nanos_per_unit) {
+        (nanos / nanos_per_unit).to_string()
+    } else {
+        (nanos as f64 / nanos_per_unit as f64).to_string()
+    }
+}
+
+macro_rules! unit {
+    ($(#[$doc:meta])* $name:ident, $per_sec:expr, $expecting:literal) => {
+        $(#[$doc])*
+        pub mod $name {
+            use crate::parse::ParseVisitor;
+            use core::{marker::PhantomData, time::Duration};
+            use serde::{Deserializer, Serializer};
+
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_str(ParseVisitor {
+                    parse: |value| super::parse(value, $per_sec),
+                    expecting: $expecting,
+                    marker: PhantomData,
+                })
+            }
+
+            pub fn serialize<S>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(&super::format(value, $per_sec))
+            }
+        }
+    };
+}
+
+unit!(
+    /// Durations in seconds, like `30` or `0.5`.
+    secs,
+    1,
+    "a number of seconds"
+);
+unit!(
+    /// Durations in milliseconds, like `250` or `1.5`.
+    millis,
+    1_000,
+    "a number of milliseconds"
+);
+unit!(
+    /// Durations in microseconds.
+    micros,
+    1_000_000,
+    "a number of microseconds"
+);
+unit!(
+    /// Durations in nanoseconds.
+    nanos,
+    1_000_000_000,
+    "a number of nanoseconds"
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use core::time::Duration;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Poll {
+        #[serde(with = "super::secs")]
+        timeout: Duration,
+        #[serde(with = "super::millis")]
+        interval: Duration,
+    }
+
+    #[test]
+    fn test_durations() {
+        for (timeout, interval, expected) in [
+            (
+                "30",
+                "250",
+                (Duration::from_secs(30), Duration::from_millis(250)),
+            ),
+            (
+                "0.5",
+                "1.5",
+                (Duration::from_millis(500), Duration::from_micros(1500)),
+            ),
+            ("1e2", "0", (Duration::from_secs(100), Duration::ZERO)),
+        ] {
+            let poll: Poll = from_slice(&[("timeout", timeout), ("interval", interval)]).unwrap();
+            assert_eq!((poll.timeout, poll.interval), expected);
+        }
+
+        assert_eq!(
+            to_pairs(&Poll {
+                timeout: Duration::from_millis(1500),
+                interval: Duration::from_secs(2),
+            })
+            .unwrap(),
+            [
+                ("timeout".to_owned(), "1.5".to_owned()),
+                ("interval".to_owned(), "2000".to_owned()),
+            ]
+        );
+
+        for timeout in ["-1", "NaN", "inf", "30s"] {
+            let err = from_slice::<Poll>(&[("timeout", timeout), ("interval", "1")]).unwrap_err();
+            assert!(
+                matches!(err, ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "timeout"),
+                "{timeout}: {err:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! `#[serde(with)]` helpers for [`Either`] params, like an ID or a slug.
+//!
+//! The value is buffered and deserialized as `L` first, falling back to `R`. This is a lighter
+//! alternative to an untagged enum, whose variants can't parse numbers from a string param.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use either::Either;
+//!
+//! #[derive(Deserialize)]
+//! struct Post {
+//!     #[serde(with = "matchit_serde::either")]
+//!     post: Either<u64, String>,
+//! }
+//!
+//! let post: Post = matchit_serde::from_slice(&[("post", "42")]).unwrap();
+//! assert_eq!(post.post, Either::Left(42));
+//! let post: Post = matchit_serde::from_slice(&[("post", "hello-world")]).unwrap();
+//! assert_eq!(post.post, Either::Right("hello-world".to_owned()));
+//! ```
+
+use crate::ValueDeserializer;
+use ::either::Either;
+use alloc::borrow::Cow;
+use core::{any::type_name, fmt, marker::PhantomData};
+use serde::{
+    Deserializer, Serialize, Serializer,
+    de::{self, DeserializeOwned},
+};
+
+struct EitherVisitor<L, R>(PhantomData<(L, R)>);
+
+impl<L, R> de::Visitor<'_> for EitherVisitor<L, R>
+where
+    L: DeserializeOwned,
+    R: DeserializeOwned,
+{
+    type Value = Either<L, R>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a {} or a {}", type_name::<L>(), type_name::<R>())
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let buffered = Cow::Borrowed(value);
+        let deserializer = || ValueDeserializer {
+            key: None,
+            value: &buffered,
+        };
+        L::deserialize(deserializer())
+            .map(Either::Left)
+            .or_else(|_| R::deserialize(deserializer()).map(Either::Right))
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+pub fn deserialize<'de, D, L, R>(deserializer: D) -> Result<Either<L, R>, D::Error>
+where
+    D: Deserializer<'de>,
+    L: DeserializeOwned,
+    R: DeserializeOwned,
+{
+    deserializer.deserialize_str(EitherVisitor(PhantomData))
+}
+
+pub fn serialize<S, L, R>(value: &Either<L, R>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    L: Serialize,
+    R: Serialize,
+{
+    match value {
+        Either::Left(value) => value.serialize(serializer),
+        Either::Right(value) => value.serialize(serializer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use alloc::string::String;
+    use either::Either;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Lookup {
+        #[serde(with = "super")]
+        user: Either<u64, String>,
+        #[serde(with = "super")]
+        flag: Either<bool, u8>,
+    }
+
+    #[test]
+    fn test_either() {
+        let lookup: Lookup = from_slice(&[("user", "42"), ("flag", "true")]).unwrap();
+        assert_eq!(lookup.user, Either::Left(42));
+        assert_eq!(lookup.flag, Either::Left(true));
+        assert_eq!(
+            to_pairs(&lookup).unwrap(),
+            [("user", "42"), ("flag", "true")].map(|(k, v)| (k.to_owned(), v.to_owned()))
+        );
+
+        let lookup: Lookup = from_slice(&[("user", "jane%20doe"), ("flag", "1")]).unwrap();
+        assert_eq!(lookup.user, Either::Right("jane doe".to_owned()));
+        assert_eq!(lookup.flag, Either::Right(1));
+
+        let err = from_slice::<Lookup>(&[("user", "1"), ("flag", "yes")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, ref value, expected_type, .. }
+                if key == "flag" && value == "yes" && expected_type.contains("Either")
+        ));
+        assert!(
+            err.to_string()
+                .ends_with(": invalid value: string \"yes\", expected a bool or a u8")
+        );
+    }
+}
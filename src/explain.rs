@@ -0,0 +1,516 @@
+use crate::{Params, ParamsDeserializationError, ParamsRefDeserializer, StructAccess};
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{cell::RefCell, fmt};
+use serde::de::{self, DeserializeSeed, MapAccess, Visitor};
+
+/// How a struct field was resolved by [`explain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldReport {
+    /// The name of the field, after renaming.
+    pub field: &'static str,
+    /// The decoded value of the param matching the field, if any.
+    pub value: Option<String>,
+    /// Whether the value is owned instead of borrowed from the input, as it is when decoding or
+    /// preprocessing the params changed it.
+    pub owned: bool,
+    /// The type the field asked the deserializer for, like `u32`, `str` or `option`.
+    pub parsed_as: Option<&'static str>,
+    /// The error deserializing the value failed with.
+    pub error: Option<String>,
+}
+
+/// The report returned by [`explain`].
+///
+/// Its `Display` impl prints one line per field, for debugging.
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    /// The fields of the struct, in order of declaration.
+    pub fields: Vec<FieldReport>,
+    /// Keys of params which don't belong to any field.
+    pub unused: Vec<String>,
+    /// The error deserializing the struct failed with.
+    pub error: Option<ParamsDeserializationError>,
+}
+
+/// Deserializes `T` from `params` and reports how each field of the struct was resolved: which
+/// param matched it, what its value was, what type it was parsed as and where it failed.
+///
+/// Fields after the one deserializing failed at aren't resolved.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::params;
+///
+/// #[derive(Deserialize)]
+/// #[allow(dead_code)]
+/// struct UserPost {
+///     user: String,
+///     post: u32,
+/// }
+///
+/// let explanation = matchit_serde::explain::<UserPost>(&params! { "user" => "alice", "id" => "7" });
+/// assert_eq!(explanation.fields[0].value.as_deref(), Some("alice"));
+/// assert_eq!(explanation.fields[1].value, None);
+/// assert_eq!(explanation.unused, ["id"]);
+/// assert_eq!(
+///     explanation.to_string(),
+///     "user: \"alice\" parsed as string\npost: missing\nunused: id\nerror: missing field `post`\n"
+/// );
+/// ```
+pub fn explain<'de, T>(params: &Params<'de>) -> Explanation
+where
+    T: de::Deserialize<'de>,
+{
+    let (result, Trace { fields, unused }) = trace::<T>(params);
+    Explanation {
+        fields,
+        unused,
+        error: result.err(),
+    }
+}
+
+/// Deserializes `T` from `params`, tracing the fields of the struct and the unused keys.
+pub(crate) fn trace<'de, T>(params: &Params<'de>) -> (Result<T, ParamsDeserializationError>, Trace)
+where
+    T: de::Deserialize<'de>,
+{
+    let trace = RefCell::new(Trace {
+        fields: Vec::new(),
+        unused: Vec::new(),
+    });
+    let result = T::deserialize(ExplainDeserializer {
+        params,
+        trace: &trace,
+    });
+    (result, trace.into_inner())
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for report in &self.fields {
+            write!(f, "{}: ", report.field)?;
+            match &report.value {
+                Some(value) => write!(f, "{value:?}")?,
+                None => f.write_str("missing")?,
+            }
+            if report.owned {
+                f.write_str(" (owned)")?;
+            }
+            if let Some(parsed_as) = report.parsed_as {
+                write!(f, " parsed as {parsed_as}")?;
+            }
+            if let Some(error) = &report.error {
+                write!(f, " failed: {error}")?;
+            }
+            f.write_str("\n")?;
+        }
+        if !self.unused.is_empty() {
+            writeln!(f, "unused: {}", self.unused.join(", "))?;
+        }
+        if let Some(error) = &self.error {
+            writeln!(f, "error: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct Trace {
+    pub(crate) fields: Vec<FieldReport>,
+    pub(crate) unused: Vec<String>,
+}
+
+/// Deserializes like [`ParamsRefDeserializer`], tracing the fields of a struct.
+struct ExplainDeserializer<'a, 'de> {
+    params: &'a Params<'de>,
+    trace: &'a RefCell<Trace>,
+}
+
+impl<'a, 'de> ExplainDeserializer<'a, 'de> {
+    fn by_ref(&self) -> ParamsRefDeserializer<'a, 'de> {
+        ParamsRefDeserializer {
+            params: self.params,
+        }
+    }
+}
+
+macro_rules! forward {
+    ($($trait_fn:ident)*) => {
+        $(
+            fn $trait_fn<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.by_ref().$trait_fn(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ExplainDeserializer<'_, 'de> {
+    type Error = ParamsDeserializationError;
+
+    forward! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32
+        deserialize_u64 deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char
+        deserialize_str deserialize_string deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.by_ref().deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.by_ref().deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.by_ref().deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.by_ref().deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.trace.borrow_mut().fields = fields
+            .iter()
+            .map(|&field| FieldReport {
+                field,
+                value: None,
+                owned: false,
+                parsed_as: None,
+                error: None,
+            })
+            .collect();
+        visitor.visit_map(ExplainAccess {
+            params: self.params,
+            inner: StructAccess::new(self.params, fields),
+            trace: self.trace,
+            field: None,
+        })
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.by_ref().deserialize_enum(name, variants, visitor)
+    }
+}
+
+struct ExplainAccess<'a, 'de> {
+    params: &'a Params<'de>,
+    inner: StructAccess<'a, 'de>,
+    trace: &'a RefCell<Trace>,
+    field: Option<usize>,
+}
+
+impl<'de> MapAccess<'de> for ExplainAccess<'_, 'de> {
+    type Error = ParamsDeserializationError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let key = self.inner.next_key_seed(seed)?;
+        let Some(idx) = self.inner.value else {
+            return Ok(key);
+        };
+        let (name, value) = &self.params.0[idx];
+        let mut trace = self.trace.borrow_mut();
+        self.field = trace.fields.iter().position(|report| report.field == name);
+        match self.field {
+            Some(field) => {
+                let report = &mut trace.fields[field];
+                report.value = Some(value.to_string());
+                report.owned = matches!(value, Cow::Owned(_));
+            }
+            None => trace.unused.push(name.to_string()),
+        }
+        Ok(key)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.field.take() {
+            Some(field) => self.inner.next_value_seed(TracedSeed {
+                seed,
+                trace: self.trace,
+                field,
+            }),
+            None => self.inner.next_value_seed(seed),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct TracedSeed<'a, S> {
+    seed: S,
+    trace: &'a RefCell<Trace>,
+    field: usize,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for TracedSeed<'_, S> {
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<S::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let result = self.seed.deserialize(TracedDeserializer {
+            inner: deserializer,
+            trace: self.trace,
+            field: self.field,
+        });
+        if let Err(err) = &result {
+            self.trace.borrow_mut().fields[self.field].error = Some(err.to_string());
+        }
+        result
+    }
+}
+
+/// Forwards to the value deserializer, recording the type the field asks for.
+struct TracedDeserializer<'a, D> {
+    inner: D,
+    trace: &'a RefCell<Trace>,
+    field: usize,
+}
+
+impl<D> TracedDeserializer<'_, D> {
+    fn parsed_as(self, ty: &'static str) -> D {
+        self.trace.borrow_mut().fields[self.field]
+            .parsed_as
+            .get_or_insert(ty);
+        self.inner
+    }
+}
+
+macro_rules! traced {
+    ($($trait_fn:ident $ty:literal)*) => {
+        $(
+            fn $trait_fn<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.parsed_as($ty).$trait_fn(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D: de::Deserializer<'de>> de::Deserializer<'de> for TracedDeserializer<'_, D> {
+    type Error = D::Error;
+
+    traced! {
+        deserialize_any "any" deserialize_bool "bool" deserialize_i8 "i8" deserialize_i16 "i16"
+        deserialize_i32 "i32" deserialize_i64 "i64" deserialize_i128 "i128" deserialize_u8 "u8"
+        deserialize_u16 "u16" deserialize_u32 "u32" deserialize_u64 "u64" deserialize_u128 "u128"
+        deserialize_f32 "f32" deserialize_f64 "f64" deserialize_char "char" deserialize_str "str"
+        deserialize_string "string" deserialize_bytes "bytes" deserialize_byte_buf "byte_buf"
+        deserialize_option "option" deserialize_unit "unit" deserialize_seq "seq"
+        deserialize_map "map" deserialize_identifier "identifier"
+        deserialize_ignored_any "ignored_any"
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.parsed_as(name).deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.parsed_as(name)
+            .deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.parsed_as("tuple").deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.parsed_as(name)
+            .deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.parsed_as(name)
+            .deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.parsed_as(name)
+            .deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Params, params};
+    use serde::Deserialize;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    struct Listing {
+        owner: String,
+        page: u32,
+        tag: Option<String>,
+        per_page: Option<u32>,
+    }
+
+    #[test]
+    fn test_explain() {
+        let params = params! { "owner" => "alice", "page" => "x", "per_page" => "5" };
+        let explanation = explain::<Listing>(&params);
+        assert_eq!(
+            explanation.fields,
+            [
+                FieldReport {
+                    field: "owner",
+                    value: Some("alice".into()),
+                    owned: false,
+                    parsed_as: Some("string"),
+                    error: None,
+                },
+                FieldReport {
+                    field: "page",
+                    value: Some("x".into()),
+                    owned: false,
+                    parsed_as: Some("u32"),
+                    error: Some("Failed to parse value \"x\" at key \"page\" as type u32".into()),
+                },
+                FieldReport {
+                    field: "tag",
+                    value: None,
+                    owned: false,
+                    parsed_as: None,
+                    error: None,
+                },
+                FieldReport {
+                    field: "per_page",
+                    value: None,
+                    owned: false,
+                    parsed_as: None,
+                    error: None,
+                },
+            ]
+        );
+        assert!(matches!(
+            explanation.error,
+            Some(ParamsDeserializationError::ParseErrorAtKey { .. })
+        ));
+    }
+
+    #[test]
+    fn test_explain_owned() {
+        let params =
+            Params::decode([("owner", "a%20b"), ("page", "2"), ("sort", "asc")].into_iter())
+                .unwrap();
+        let explanation = explain::<Listing>(&params);
+        assert!(explanation.error.is_none());
+        assert!(explanation.fields[0].owned);
+        assert_eq!(explanation.fields[3].parsed_as, None);
+        assert_eq!(explanation.unused, ["sort"]);
+        assert_eq!(
+            explanation.to_string(),
+            "owner: \"a b\" (owned) parsed as string\n\
+             page: \"2\" parsed as u32\n\
+             tag: missing\n\
+             per_page: missing\n\
+             unused: sort\n"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use crate::parse::ParseVisitor;
+use core::{fmt, marker::PhantomData, ops::Deref, str::FromStr};
+use serde::{de, ser};
+
+/// A param parsed with the [`FromStr`] implementation of `T`, for types which don't implement
+/// `Deserialize` or deserialize from something other than a string. Serialized with the
+/// [`Display`](fmt::Display) implementation of `T`.
+///
+/// A value `T` can't be parsed from fails with a parse error at its key.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::FromStrParam;
+/// use std::str::FromStr;
+///
+/// struct Color(u8, u8, u8);
+///
+/// impl FromStr for Color {
+///     type Err = std::num::ParseIntError;
+///
+///     fn from_str(value: &str) -> Result<Self, Self::Err> {
+///         let rgb = u32::from_str_radix(value, 16)?;
+///         Ok(Color((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+///     }
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Theme {
+///     accent: FromStrParam<Color>,
+/// }
+///
+/// let theme: Theme = matchit_serde::from_slice(&[("accent", "ff8000")]).unwrap();
+/// assert_eq!(theme.accent.1, 0x80);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FromStrParam<T>(pub T);
+
+impl<T> FromStrParam<T> {
+    /// Returns the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for FromStrParam<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'de, T: FromStr> de::Deserialize<'de> for FromStrParam<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ParseVisitor {
+            parse: |value| value.parse().ok().map(FromStrParam),
+            expecting: "a value parsed with FromStr",
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<T: fmt::Display> ser::Serialize for FromStrParam<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq)]
+    struct Version(u16, u16);
+
+    impl FromStr for Version {
+        type Err = ();
+
+        fn from_str(value: &str) -> Result<Self, ()> {
+            let (major, minor) = value.split_once('.').ok_or(())?;
+            Ok(Self(
+                major.parse().map_err(drop)?,
+                minor.parse().map_err(drop)?,
+            ))
+        }
+    }
+
+    impl fmt::Display for Version {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}.{}", self.0, self.1)
+        }
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Release {
+        version: FromStrParam<Version>,
+    }
+
+    #[test]
+    fn test_from_str_param() {
+        let release: Release = from_slice(&[("version", "1.2")]).unwrap();
+        assert_eq!(*release.version, Version(1, 2));
+        assert_eq!(
+            to_pairs(&release).unwrap(),
+            [("version".to_owned(), "1.2".to_owned())]
+        );
+
+        let err = from_slice::<Release>(&[("version", "1")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "version"
+        ));
+    }
+}
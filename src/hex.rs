@@ -0,0 +1,158 @@
+//! `#[serde(with)]` helpers for hex params, like git SHAs or content hashes.
+//!
+//! Upper and lowercase digits are accepted. A value of the wrong length fails with
+//! [`InvalidLength`](crate::ParamsDeserializationError::InvalidLength) and a non-hex character
+//! with [`InvalidCharacter`](crate::ParamsDeserializationError::InvalidCharacter).
+//!
+//! ```
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Commit {
+//!     #[serde(with = "matchit_serde::hex")]
+//!     sha: [u8; 20],
+//! }
+//!
+//! let commit: Commit =
+//!     matchit_serde::from_slice(&[("sha", "3A0E428C0FFEE0123456789ABCDEF0123456789a")]).unwrap();
+//! assert_eq!(commit.sha[..2], [0x3a, 0x0e]);
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, marker::PhantomData};
+use serde::{Deserializer, Serializer, de};
+
+/// Byte containers that can be decoded from hex.
+pub trait HexBytes: AsRef<[u8]> + Sized {
+    /// The required number of bytes, if fixed.
+    const LEN: Option<usize>;
+
+    /// Builds the value from exactly [`LEN`](HexBytes::LEN) bytes, if given.
+    fn from_bytes(bytes: Vec<u8>) -> Self;
+}
+
+impl HexBytes for Vec<u8> {
+    const LEN: Option<usize> = None;
+
+    fn from_bytes(bytes: Vec<u8>) -> Self {
+        bytes
+    }
+}
+
+impl<const N: usize> HexBytes for [u8; N] {
+    const LEN: Option<usize> = Some(N);
+
+    fn from_bytes(bytes: Vec<u8>) -> Self {
+        bytes.try_into().expect("length is checked before")
+    }
+}
+
+struct HexVisitor<T>(PhantomData<T>);
+
+impl<T: HexBytes> de::Visitor<'_> for HexVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match T::LEN {
+            Some(len) => write!(f, "{} hex characters", len * 2),
+            None => f.write_str("an even number of hex characters"),
+        }
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        if let Some(character) = value.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(E::invalid_value(
+                de::Unexpected::Char(character),
+                &"a hex digit",
+            ));
+        }
+        if !value.len().is_multiple_of(2) || T::LEN.is_some_and(|len| value.len() != len * 2) {
+            return Err(E::invalid_length(value.len(), &self));
+        }
+        let bytes = value
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| (digit(pair[0]) << 4) | digit(pair[1]))
+            .collect();
+        Ok(T::from_bytes(bytes))
+    }
+}
+
+fn digit(character: u8) -> u8 {
+    match character {
+        b'0'..=b'9' => character - b'0',
+        b'a'..=b'f' => character - b'a' + 10,
+        _ => character - b'A' + 10,
+    }
+}
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: HexBytes,
+{
+    deserializer.deserialize_str(HexVisitor(PhantomData))
+}
+
+/// Serializes the bytes as lowercase hex.
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: AsRef<[u8]>,
+{
+    let value: String = value
+        .as_ref()
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0xf])
+        .map(|digit| char::from_digit(u32::from(digit), 16).unwrap())
+        .collect();
+    serializer.serialize_str(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use alloc::vec::Vec;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Blob {
+        #[serde(with = "super")]
+        hash: [u8; 4],
+        #[serde(with = "super")]
+        data: Vec<u8>,
+    }
+
+    #[test]
+    fn test_hex() {
+        let blob: Blob = from_slice(&[("hash", "DeadBeef"), ("data", "")]).unwrap();
+        assert_eq!(blob.hash, [0xde, 0xad, 0xbe, 0xef]);
+        assert!(blob.data.is_empty());
+        let blob: Blob = from_slice(&[("hash", "00ff00ff"), ("data", "0a0B")]).unwrap();
+        assert_eq!(blob.data, [0x0a, 0x0b]);
+        assert_eq!(
+            to_pairs(&blob).unwrap(),
+            [("hash", "00ff00ff"), ("data", "0a0b")].map(|(k, v)| (k.to_owned(), v.to_owned()))
+        );
+
+        let err = from_slice::<Blob>(&[("hash", "deadbe"), ("data", "")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::InvalidLength { got: 6, ref expected, .. }
+                if expected == "8 hex characters"
+        ));
+        assert_eq!(err.key(), Some("hash"));
+        let err = from_slice::<Blob>(&[("hash", "deadbeef"), ("data", "abc")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::InvalidLength { got: 3, .. }
+        ));
+        let err = from_slice::<Blob>(&[("hash", "deadbeeg"), ("data", "")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::InvalidCharacter { character: 'g', .. }
+        ));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{Params, ParamsBuf, ParamsDeserializationError, ParamsDeserializer};
+use ::http::{Extensions, HeaderMap};
+use alloc::{borrow::Cow, vec::Vec};
+use core::{
+    ops::{Deref, DerefMut},
+    str::Utf8Error,
+};
+use serde::de::IntoDeserializer;
+
+/// Params deserialized from the [`ParamsBuf`] a routing layer stored in [`Extensions`].
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::{ExtensionParams, ParamsBuf};
+///
+/// #[derive(Deserialize)]
+/// struct UserPost {
+///     user: String,
+///     post: u32,
+/// }
+///
+/// // in the routing layer
+/// let mut router = matchit::Router::new();
+/// router.insert("/users/{user}/posts/{post}", ()).unwrap();
+/// let matched = router.at("/users/alice/posts/42").unwrap();
+/// let mut request = http::Request::new(());
+/// request
+///     .extensions_mut()
+///     .insert(ParamsBuf::try_from(&matched.params).unwrap());
+///
+/// // in the handler
+/// let ExtensionParams(params): ExtensionParams<UserPost> =
+///     ExtensionParams::from_extensions(request.extensions()).unwrap();
+/// assert_eq!(params.user, "alice");
+/// assert_eq!(params.post, 42);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtensionParams<T>(pub T);
+
+impl<T> ExtensionParams<T> {
+    /// Deserializes `T` from the params in `extensions`.
+    ///
+    /// Fails with [`ParamsDeserializationError::MissingParams`] if no [`ParamsBuf`] was stored.
+    pub fn from_extensions<'de>(
+        extensions: &'de Extensions,
+    ) -> Result<Self, ParamsDeserializationError>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        extensions
+            .get::<ParamsBuf>()
+            .ok_or(ParamsDeserializationError::MissingParams)?
+            .deserialize()
+            .map(Self)
+    }
+
+    /// Returns the deserialized params.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for ExtensionParams<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for ExtensionParams<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Uses the headers as params, keyed by their lowercase names.
+///
+/// The values are used as they are, without percent-decoding. Headers with multiple values result
+/// in multiple params with the same key. Fails if a value isn't valid UTF-8.
+impl<'de> TryFrom<&'de HeaderMap> for Params<'de> {
+    type Error = ParamsDeserializationError;
+
+    fn try_from(headers: &'de HeaderMap) -> Result<Self, Self::Error> {
+        Ok(Self(
+            headers
+                .iter()
+                .map(|(name, value)| {
+                    Ok((
+                        Cow::Borrowed(name.as_str()),
+                        Cow::Borrowed(core::str::from_utf8(value.as_bytes())?),
+                    ))
+                })
+                .collect::<Result<Vec<_>, Utf8Error>>()?,
+        ))
+    }
+}
+
+/// Deserializes `T` from request headers, keyed by their lowercase names.
+///
+/// ```
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Routing {
+///     #[serde(rename = "x-tenant")]
+///     tenant: String,
+///     #[serde(rename = "x-shard")]
+///     shard: u8,
+/// }
+///
+/// let mut headers = http::HeaderMap::new();
+/// headers.insert("X-Tenant", "acme".parse().unwrap());
+/// headers.insert("X-Shard", "3".parse().unwrap());
+///
+/// let routing: Routing = matchit_serde::from_headers(&headers).unwrap();
+/// assert_eq!(routing.tenant, "acme");
+/// assert_eq!(routing.shard, 3);
+/// ```
+pub fn from_headers<'de, T>(headers: &'de HeaderMap) -> Result<T, ParamsDeserializationError>
+where
+    T: serde::Deserialize<'de>,
+{
+    ParamsDeserializer::new(Params::try_from(headers)?).deserialize()
+}
+
+/// Deserializes `T` from `params` and stores it in `extensions`, to be retrieved with
+/// [`get_typed_params`].
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::{get_typed_params, insert_typed_params, params};
+///
+/// #[derive(Clone, Deserialize)]
+/// struct User {
+///     id: u32,
+/// }
+///
+/// let mut extensions = http::Extensions::new();
+/// insert_typed_params::<User>(&mut extensions, &params! { "id" => "42" }).unwrap();
+/// assert_eq!(get_typed_params::<User>(&extensions).unwrap().id, 42);
+/// ```
+pub fn insert_typed_params<T>(
+    extensions: &mut Extensions,
+    params: &Params<'_>,
+) -> Result<(), ParamsDeserializationError>
+where
+    T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    extensions.insert(T::deserialize(params.into_deserializer())?);
+    Ok(())
+}
+
+/// Returns the params stored in `extensions` by [`insert_typed_params`].
+pub fn get_typed_params<T>(extensions: &Extensions) -> Option<&T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    extensions.get::<T>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExtensionParams, get_typed_params, insert_typed_params};
+    use crate::{Params, ParamsBuf, ParamsDeserializationError, params};
+    use http::{Extensions, HeaderMap, HeaderValue};
+
+    #[test]
+    fn test_from_extensions() {
+        let mut extensions = Extensions::new();
+        extensions.insert(ParamsBuf::from(vec![("id".to_owned(), "7".to_owned())]));
+        let ExtensionParams(id) = ExtensionParams::<u32>::from_extensions(&extensions).unwrap();
+        assert_eq!(id, 7);
+    }
+
+    #[test]
+    fn test_missing_params() {
+        assert!(matches!(
+            ExtensionParams::<u32>::from_extensions(&Extensions::new()),
+            Err(ParamsDeserializationError::MissingParams)
+        ));
+    }
+
+    #[test]
+    fn test_headers() {
+        let mut headers = HeaderMap::new();
+        headers.append("x-id", HeaderValue::from_static("1"));
+        headers.append("x-id", HeaderValue::from_static("2"));
+        headers.append("x-name", HeaderValue::from_static("a%20b"));
+        let params = Params::try_from(&headers).unwrap();
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("x-id", "1"), ("x-id", "2"), ("x-name", "a%20b")]
+        );
+
+        headers.insert("x-bin", HeaderValue::from_bytes(b"\xff").unwrap());
+        assert!(matches!(
+            Params::try_from(&headers),
+            Err(ParamsDeserializationError::Utf8Error(_))
+        ));
+    }
+
+    #[test]
+    fn test_typed_params() {
+        let mut extensions = Extensions::new();
+        insert_typed_params::<(u32, String)>(
+            &mut extensions,
+            &params! { "id" => "7", "name" => "x" },
+        )
+        .unwrap();
+        assert_eq!(
+            get_typed_params::<(u32, String)>(&extensions),
+            Some(&(7, "x".to_owned()))
+        );
+
+        assert!(insert_typed_params::<(bool,)>(&mut extensions, &params! { "id" => "7" }).is_err());
+        assert!(get_typed_params::<(bool,)>(&extensions).is_none());
+    }
+}
@@ -0,0 +1,71 @@
+//! `#[serde(with)]` helpers for human-readable [`Duration`] params like `30s`, `5m` or `2h 30m`.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use std::time::Duration;
+//!
+//! #[derive(Deserialize)]
+//! struct Retention {
+//!     #[serde(with = "matchit_serde::humantime")]
+//!     window: Duration,
+//! }
+//!
+//! let retention: Retention = matchit_serde::from_slice(&[("window", "1h%2030m")]).unwrap();
+//! assert_eq!(retention.window, Duration::from_secs(5400));
+//! ```
+
+use crate::parse::ParseVisitor;
+use core::{marker::PhantomData, time::Duration};
+use serde::{Deserializer, Serializer};
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |value| ::humantime::parse_duration(value).ok(),
+        expecting: "a duration like `30s` or `2h 30m`",
+        marker: PhantomData,
+    })
+}
+
+pub fn serialize<S>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(&::humantime::format_duration(*value))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use core::time::Duration;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Retention {
+        #[serde(with = "super")]
+        window: Duration,
+    }
+
+    #[test]
+    fn test_durations() {
+        for (value, secs) in [("30s", 30), ("5m", 300), ("2h", 7200), ("1d", 86400)] {
+            let retention: Retention = from_slice(&[("window", value)]).unwrap();
+            assert_eq!(retention.window, Duration::from_secs(secs));
+        }
+        assert_eq!(
+            to_pairs(&Retention {
+                window: Duration::from_secs(5400)
+            })
+            .unwrap(),
+            [("window".to_owned(), "1h 30m".to_owned())]
+        );
+
+        let err = from_slice::<Retention>(&[("window", "30")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "window"
+        ));
+    }
+}
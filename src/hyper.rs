@@ -0,0 +1,280 @@
+//! A minimal typed router for bare hyper 1.x servers.
+
+use crate::ParamsBuf;
+use alloc::{boxed::Box, string::String, sync::Arc};
+use core::{convert::Infallible, future::Future, pin::Pin};
+use http::{Request, Response, StatusCode};
+use serde::de::DeserializeOwned;
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+type Handler<ReqB, ResB> =
+    Arc<dyn Fn(Request<ReqB>, ParamsBuf) -> BoxFuture<Response<ResB>> + Send + Sync>;
+
+/// Routes requests to async handlers taking the params deserialized into their own type.
+///
+/// Requests which don't match a route get a `404 Not Found` response, requests whose params can't
+/// be decoded or deserialized get a `400 Bad Request` response with the error message as body.
+///
+/// The router implements [`hyper::service::Service`] and is cheap to clone, so it can be passed
+/// to `serve_connection` directly.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use http::{Request, Response};
+/// use matchit_serde::hyper::TypedRouter;
+///
+/// #[derive(Deserialize)]
+/// struct UserPost {
+///     user: String,
+///     post: u32,
+/// }
+///
+/// let router: TypedRouter<(), String> = TypedRouter::new()
+///     .route("/users/{user}/posts/{post}", |_: Request<()>, params: UserPost| async move {
+///         Response::new(format!("post {} by {}", params.post, params.user))
+///     })
+///     .unwrap();
+/// ```
+pub struct TypedRouter<ReqB, ResB> {
+    router: Arc<matchit::Router<Handler<ReqB, ResB>>>,
+}
+
+impl<ReqB, ResB> TypedRouter<ReqB, ResB>
+where
+    ReqB: 'static,
+    ResB: From<String> + Send + 'static,
+{
+    /// Creates a router without routes.
+    pub fn new() -> Self {
+        Self {
+            router: Arc::new(matchit::Router::new()),
+        }
+    }
+
+    /// Adds a route handled by `handler`, which gets the params deserialized into `P`.
+    ///
+    /// If `P` has a field renamed to [`RAW_PATH`](crate::RAW_PATH), it gets the path of the
+    /// request, and if it has one renamed to [`TEMPLATE`](crate::TEMPLATE), it gets `template`.
+    pub fn route<P, F, Fut>(
+        mut self,
+        template: impl Into<String>,
+        handler: F,
+    ) -> Result<Self, matchit::InsertError>
+    where
+        P: DeserializeOwned,
+        F: Fn(Request<ReqB>, P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response<ResB>> + Send + 'static,
+    {
+        let template: String = template.into();
+        let route = template.clone();
+        let handler: Handler<ReqB, ResB> = Arc::new(move |req, params| {
+            match params.deserializer().deserialize_route::<P>(&route) {
+                Ok(params) => Box::pin(handler(req, params)),
+                Err(err) => Box::pin(core::future::ready(error_response(
+                    StatusCode::BAD_REQUEST,
+                    err.to_string(),
+                ))),
+            }
+        });
+        Arc::make_mut(&mut self.router).insert(template, handler)?;
+        Ok(self)
+    }
+
+    /// Routes `req` to the matching handler.
+    pub fn handle(&self, req: Request<ReqB>) -> BoxFuture<Response<ResB>> {
+        let params = match self.router.at(req.uri().path()) {
+            Ok(matched) => ParamsBuf::try_from(&matched.params)
+                .map(|params| (matched.value, params.with_raw_path(req.uri().path()))),
+            Err(_) => {
+                return Box::pin(core::future::ready(error_response(
+                    StatusCode::NOT_FOUND,
+                    String::from("Not Found"),
+                )));
+            }
+        };
+        match params {
+            Ok((handler, params)) => handler(req, params),
+            Err(err) => Box::pin(core::future::ready(error_response(
+                StatusCode::BAD_REQUEST,
+                err.to_string(),
+            ))),
+        }
+    }
+}
+
+fn error_response<B>(status: StatusCode, message: String) -> Response<B>
+where
+    B: From<String>,
+{
+    let mut response = Response::new(B::from(message));
+    *response.status_mut() = status;
+    response
+}
+
+impl<ReqB, ResB> Default for TypedRouter<ReqB, ResB>
+where
+    ReqB: 'static,
+    ResB: From<String> + Send + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<ReqB, ResB> Clone for TypedRouter<ReqB, ResB> {
+    fn clone(&self) -> Self {
+        Self {
+            router: self.router.clone(),
+        }
+    }
+}
+
+impl<ReqB, ResB> core::fmt::Debug for TypedRouter<ReqB, ResB> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TypedRouter").finish_non_exhaustive()
+    }
+}
+
+impl<ReqB, ResB> ::hyper::service::Service<Request<ReqB>> for TypedRouter<ReqB, ResB>
+where
+    ReqB: 'static,
+    ResB: From<String> + Send + 'static,
+{
+    type Response = Response<ResB>;
+    type Error = Infallible;
+    type Future = BoxFuture<Result<Response<ResB>, Infallible>>;
+
+    fn call(&self, req: Request<ReqB>) -> Self::Future {
+        let response = self.handle(req);
+        Box::pin(async move { Ok(response.await) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypedRouter;
+    use crate::test_util::poll_once;
+    use http::{Request, Response, StatusCode};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct User {
+        id: u32,
+    }
+
+    fn call(router: &TypedRouter<(), String>, path: &str) -> (StatusCode, String) {
+        let response = poll_once(router.handle(Request::get(path).body(()).unwrap()));
+        (response.status(), response.into_body())
+    }
+
+    #[test]
+    fn test_routing() {
+        let router = TypedRouter::new()
+            .route("/users/{id}", |_, user: User| async move {
+                Response::new(format!("user {}", user.id))
+            })
+            .unwrap();
+
+        assert_eq!(
+            call(&router, "/users/7"),
+            (StatusCode::OK, "user 7".to_owned())
+        );
+        assert_eq!(
+            call(&router, "/users/x"),
+            (
+                StatusCode::BAD_REQUEST,
+                "Failed to parse value \"x\" at key \"id\" as type u32".to_owned()
+            )
+        );
+        assert_eq!(call(&router, "/users/%FF").0, StatusCode::BAD_REQUEST);
+        assert_eq!(call(&router, "/posts/1").0, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_route_after_clone() {
+        let router = TypedRouter::new()
+            .route("/users/{id}", |_, user: User| async move {
+                Response::new(format!("user {}", user.id))
+            })
+            .unwrap();
+        let extended = router
+            .clone()
+            .route("/posts/{id}", |_, post: User| async move {
+                Response::new(format!("post {}", post.id))
+            })
+            .unwrap();
+
+        assert_eq!(
+            call(&extended, "/posts/1"),
+            (StatusCode::OK, "post 1".to_owned())
+        );
+        assert_eq!(
+            call(&extended, "/users/7"),
+            (StatusCode::OK, "user 7".to_owned())
+        );
+        assert_eq!(call(&router, "/posts/1").0, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_raw_path() {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct File {
+            name: String,
+            #[serde(rename = "$raw_path")]
+            path: String,
+        }
+
+        let router = TypedRouter::new()
+            .route("/files/{name}", |_, file: File| async move {
+                Response::new(format!("{} at {}", file.name, file.path))
+            })
+            .unwrap()
+            .route("/users/{id}", |_, user: User| async move {
+                Response::new(format!("user {}", user.id))
+            })
+            .unwrap();
+
+        assert_eq!(
+            call(&router, "/files/a%20b"),
+            (StatusCode::OK, "a b at /files/a%20b".to_owned())
+        );
+        assert_eq!(
+            call(&router, "/users/7"),
+            (StatusCode::OK, "user 7".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_template() {
+        #[derive(Deserialize)]
+        struct Post {
+            post: u32,
+            #[serde(rename = "$template")]
+            route: String,
+        }
+
+        let router = TypedRouter::new()
+            .route("/users/{user}/posts/{post}", |_, post: Post| async move {
+                Response::new(format!("post {} via {}", post.post, post.route))
+            })
+            .unwrap()
+            .route("/users/{id}", |_, user: User| async move {
+                Response::new(format!("user {}", user.id))
+            })
+            .unwrap();
+
+        assert_eq!(
+            call(&router, "/users/alice/posts/3"),
+            (
+                StatusCode::OK,
+                "post 3 via /users/{user}/posts/{post}".to_owned()
+            )
+        );
+        assert_eq!(
+            call(&router, "/users/7"),
+            (StatusCode::OK, "user 7".to_owned())
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! `#[serde(with)]` helpers for ISO 3166-1 [`CountryCode`] params, like `/{country}/stores`.
+//!
+//! Both alpha-2 and alpha-3 codes are accepted, in any case. Codes are serialized as alpha-2.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use isocountry::CountryCode;
+//!
+//! #[derive(Deserialize)]
+//! struct Stores {
+//!     #[serde(with = "matchit_serde::isocountry")]
+//!     country: CountryCode,
+//! }
+//!
+//! let stores: Stores = matchit_serde::from_slice(&[("country", "de")]).unwrap();
+//! assert_eq!(stores.country, CountryCode::DEU);
+//! ```
+
+use crate::parse::ParseVisitor;
+use ::isocountry::CountryCode;
+use core::marker::PhantomData;
+use serde::{Deserializer, Serializer};
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<CountryCode, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |value| match value.len() {
+            2 => CountryCode::for_alpha2_caseless(value).ok(),
+            3 => CountryCode::for_alpha3_caseless(value).ok(),
+            _ => None,
+        },
+        expecting: "an ISO 3166-1 alpha-2 or alpha-3 country code",
+        marker: PhantomData,
+    })
+}
+
+pub fn serialize<S>(value: &CountryCode, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(value.alpha2())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use isocountry::CountryCode;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Stores {
+        #[serde(with = "super")]
+        country: CountryCode,
+    }
+
+    #[test]
+    fn test_country_codes() {
+        for value in ["CH", "ch", "CHE", "che"] {
+            let stores: Stores = from_slice(&[("country", value)]).unwrap();
+            assert_eq!(stores.country, CountryCode::CHE);
+        }
+        let stores = Stores {
+            country: CountryCode::USA,
+        };
+        assert_eq!(
+            to_pairs(&stores).unwrap(),
+            [("country".to_owned(), "US".to_owned())]
+        );
+
+        for value in ["XX", "XXX", "C", "Switzerland"] {
+            let err = from_slice::<Stores>(&[("country", value)]).unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                    if key == "country" && expected_type.ends_with("CountryCode")
+            ));
+        }
+    }
+}
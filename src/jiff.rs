@@ -0,0 +1,264 @@
+//! Custom formats for `jiff` params.
+//!
+//! [`civil::Date`], [`civil::DateTime`], [`Timestamp`] and [`Zoned`] params deserialize from
+//! jiff's default formats, like `2024-02-29`, `2024-02-29T12:30:00Z` or
+//! `2024-02-29T12:30:00+01:00[Europe/Paris]`, without any wrapper. Use [`Strptime`] to accept
+//! other formats.
+
+use ::jiff::{
+    Timestamp, Zoned, civil,
+    fmt::strtime::{self, BrokenDownTime},
+};
+use alloc::string::ToString;
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+use serde::{de, ser};
+
+/// The `strftime`-style formats accepted by [`Strptime`].
+pub trait Formats {
+    /// The accepted formats, tried in order. The first one is used for serialization.
+    const FORMATS: &'static [&'static str];
+}
+
+/// A jiff value parsed with the first matching of `F::FORMATS`.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use jiff::civil::Date;
+/// use matchit_serde::jiff::{Formats, Strptime};
+///
+/// struct Compact;
+/// impl Formats for Compact {
+///     const FORMATS: &'static [&'static str] = &["%Y%m%d", "%Y-%m-%d"];
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Report {
+///     day: Strptime<Date, Compact>,
+/// }
+///
+/// let report: Report = matchit_serde::from_slice(&[("day", "20240229")]).unwrap();
+/// assert_eq!(*report.day, jiff::civil::date(2024, 2, 29));
+/// ```
+pub struct Strptime<T, F>(pub T, PhantomData<F>);
+
+impl<T, F> Strptime<T, F> {
+    /// Wraps `value`, for example to serialize it with the formats of `F`.
+    pub fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Returns the parsed value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Values which can be built from a parsed [`BrokenDownTime`].
+pub trait StrptimeValue: Sized {
+    /// Builds the value from the parsed fields.
+    fn from_broken_down_time(tm: &BrokenDownTime) -> Result<Self, ::jiff::Error>;
+
+    /// Formats the value with `format`.
+    fn strftime(&self, format: &str) -> impl fmt::Display;
+}
+
+impl StrptimeValue for civil::Date {
+    fn from_broken_down_time(tm: &BrokenDownTime) -> Result<Self, ::jiff::Error> {
+        tm.to_date()
+    }
+
+    fn strftime(&self, format: &str) -> impl fmt::Display {
+        civil::Date::strftime(self, format)
+    }
+}
+
+impl StrptimeValue for civil::DateTime {
+    fn from_broken_down_time(tm: &BrokenDownTime) -> Result<Self, ::jiff::Error> {
+        tm.to_datetime()
+    }
+
+    fn strftime(&self, format: &str) -> impl fmt::Display {
+        civil::DateTime::strftime(self, format)
+    }
+}
+
+impl StrptimeValue for Timestamp {
+    fn from_broken_down_time(tm: &BrokenDownTime) -> Result<Self, ::jiff::Error> {
+        tm.to_timestamp()
+    }
+
+    fn strftime(&self, format: &str) -> impl fmt::Display {
+        Timestamp::strftime(self, format)
+    }
+}
+
+impl StrptimeValue for Zoned {
+    fn from_broken_down_time(tm: &BrokenDownTime) -> Result<Self, ::jiff::Error> {
+        tm.to_zoned()
+    }
+
+    fn strftime(&self, format: &str) -> impl fmt::Display {
+        Zoned::strftime(self, format)
+    }
+}
+
+impl<'de, T, F> de::Deserialize<'de> for Strptime<T, F>
+where
+    T: StrptimeValue,
+    F: Formats,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrptimeVisitor(PhantomData))
+    }
+}
+
+struct StrptimeVisitor<T, F>(PhantomData<(T, F)>);
+
+impl<T, F> de::Visitor<'_> for StrptimeVisitor<T, F>
+where
+    T: StrptimeValue,
+    F: Formats,
+{
+    type Value = Strptime<T, F>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a date or time in one of the formats {:?}", F::FORMATS)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        F::FORMATS
+            .iter()
+            .find_map(|format| {
+                let tm = strtime::parse(format, value).ok()?;
+                T::from_broken_down_time(&tm).ok()
+            })
+            .map(Strptime::new)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+impl<T, F> ser::Serialize for Strptime<T, F>
+where
+    T: StrptimeValue,
+    F: Formats,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let format = F::FORMATS
+            .first()
+            .ok_or_else(|| ser::Error::custom("no formats given"))?;
+        serializer.serialize_str(&self.0.strftime(format).to_string())
+    }
+}
+
+impl<T, F> Deref for Strptime<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, F> DerefMut for Strptime<T, F> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Strptime<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Strptime").field(&self.0).finish()
+    }
+}
+
+impl<T: Clone, F> Clone for Strptime<T, F> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<T: PartialEq, F> PartialEq for Strptime<T, F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq, F> Eq for Strptime<T, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Formats, Strptime};
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use jiff::{Timestamp, Zoned, civil};
+    use serde::{Deserialize, Serialize};
+
+    struct Slashes;
+
+    impl Formats for Slashes {
+        const FORMATS: &'static [&'static str] = &["%d/%m/%Y", "%Y-%m-%d"];
+    }
+
+    struct Epoch;
+
+    impl Formats for Epoch {
+        const FORMATS: &'static [&'static str] = &["%s"];
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Defaults {
+        day: civil::Date,
+        at: Timestamp,
+        zoned: Zoned,
+    }
+
+    #[test]
+    fn test_default_formats() {
+        let params: Defaults = from_slice(&[
+            ("day", "2024-02-29"),
+            ("at", "2024-02-29T12:30:00Z"),
+            ("zoned", "2024-02-29T13:30:00%2B01:00%5BEurope%2FParis%5D"),
+        ])
+        .unwrap();
+        assert_eq!(params.day, civil::date(2024, 2, 29));
+        assert_eq!(params.zoned.timestamp(), params.at);
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Custom {
+        day: Strptime<civil::Date, Slashes>,
+        at: Strptime<Timestamp, Epoch>,
+    }
+
+    #[test]
+    fn test_strptime() {
+        let params: Custom =
+            from_slice(&[("day", "29%2F02%2F2024"), ("at", "1709209800")]).unwrap();
+        assert_eq!(*params.day, civil::date(2024, 2, 29));
+        assert_eq!(params.at.as_second(), 1709209800);
+        let params: Custom = from_slice(&[("day", "2024-02-29"), ("at", "0")]).unwrap();
+        assert_eq!(*params.day, civil::date(2024, 2, 29));
+
+        assert_eq!(
+            to_pairs(&params).unwrap(),
+            [("day", "29/02/2024"), ("at", "0")].map(|(k, v)| (k.to_owned(), v.to_owned()))
+        );
+
+        let err = from_slice::<Custom>(&[("day", "2024/02/29"), ("at", "0")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "day"
+        ));
+    }
+}
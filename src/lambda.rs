@@ -0,0 +1,104 @@
+use crate::{ParamsBuf, ParamsDeserializationError};
+use alloc::{string::String, vec::Vec};
+use aws_lambda_events::apigw::{
+    ApiGatewayProxyRequest, ApiGatewayV2httpRequest, ApiGatewayWebsocketProxyRequest,
+};
+use std::collections::HashMap;
+
+/// Takes ownership of API Gateway path parameters without copying them.
+///
+/// API Gateway already decodes the values, so they are used as they are. The params are sorted by
+/// key since the map has no meaningful order, which keeps tuple and sequence deserialization
+/// deterministic.
+impl From<HashMap<String, String>> for ParamsBuf {
+    fn from(map: HashMap<String, String>) -> Self {
+        let mut params: Vec<_> = map.into_iter().collect();
+        params.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        Self(params)
+    }
+}
+
+/// API Gateway events carrying `pathParameters`.
+pub trait PathParameters {
+    /// Returns the path parameters of the event.
+    fn path_parameters(&self) -> &HashMap<String, String>;
+
+    /// Deserializes `T` from the path parameters of the event.
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// use aws_lambda_events::apigw::ApiGatewayProxyRequest;
+    /// use matchit_serde::PathParameters;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct UserPost {
+    ///     user: String,
+    ///     post: u32,
+    /// }
+    ///
+    /// let mut event = ApiGatewayProxyRequest::default();
+    /// event.path_parameters.insert("user".into(), "alice".into());
+    /// event.path_parameters.insert("post".into(), "42".into());
+    ///
+    /// let params: UserPost = event.deserialize_path().unwrap();
+    /// assert_eq!(params.user, "alice");
+    /// assert_eq!(params.post, 42);
+    /// ```
+    fn deserialize_path<'de, T>(&'de self) -> Result<T, ParamsDeserializationError>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        let params = self
+            .path_parameters()
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()));
+        crate::ParamsDeserializer::new(crate::Params::from_decoded_map(params)).deserialize()
+    }
+}
+
+macro_rules! impl_path_parameters {
+    ($($event:ty),*) => {
+        $(impl PathParameters for $event {
+            fn path_parameters(&self) -> &HashMap<String, String> {
+                &self.path_parameters
+            }
+        })*
+    };
+}
+
+impl_path_parameters!(
+    ApiGatewayProxyRequest,
+    ApiGatewayV2httpRequest,
+    ApiGatewayWebsocketProxyRequest
+);
+
+#[cfg(test)]
+mod tests {
+    use super::PathParameters;
+    use crate::ParamsBuf;
+    use aws_lambda_events::apigw::ApiGatewayV2httpRequest;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_hash_map() {
+        let map = HashMap::from([
+            ("b".to_owned(), "2".to_owned()),
+            ("a".to_owned(), "a%20b".to_owned()),
+        ]);
+        let params = ParamsBuf::from(map);
+        assert_eq!(
+            params.as_params().iter().collect::<Vec<_>>(),
+            vec![("a", "a%20b"), ("b", "2")]
+        );
+        assert_eq!(params.deserialize::<(String, u32)>().unwrap().1, 2);
+    }
+
+    #[test]
+    fn test_v2_event() {
+        let mut event = ApiGatewayV2httpRequest::default();
+        event
+            .path_parameters
+            .insert("id".to_owned(), "7".to_owned());
+        assert_eq!(event.deserialize_path::<u64>().unwrap(), 7);
+    }
+}
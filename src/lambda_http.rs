@@ -0,0 +1,73 @@
+use crate::{Params, ParamsDeserializationError, ParamsDeserializer};
+use ::lambda_http::{Request, RequestExt};
+
+/// Deserializes `T` from the path parameters of a `lambda_http` request.
+///
+/// API Gateway already decodes the values, so they are used as they are, like by the `lambda`
+/// feature. The parameters are sorted by key since the underlying map has no meaningful order.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use lambda_http::{Request, RequestExt};
+/// use std::collections::HashMap;
+///
+/// #[derive(Deserialize)]
+/// struct UserPost {
+///     user: String,
+///     post: u32,
+/// }
+///
+/// let request = Request::default().with_path_parameters(HashMap::from([
+///     ("user".to_owned(), "alice b".to_owned()),
+///     ("post".to_owned(), "42".to_owned()),
+/// ]));
+///
+/// let params: UserPost = matchit_serde::from_lambda_request(&request).unwrap();
+/// assert_eq!(params.user, "alice b");
+/// assert_eq!(params.post, 42);
+/// ```
+pub fn from_lambda_request<'de, T>(req: &'de Request) -> Result<T, ParamsDeserializationError>
+where
+    T: serde::Deserialize<'de>,
+{
+    let params = match req.path_parameters_ref() {
+        Some(params) => Params::from_decoded_map(params.iter()),
+        None => Params(alloc::vec::Vec::new()),
+    };
+    ParamsDeserializer::new(params).deserialize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_lambda_request;
+    use crate::ParamsDeserializationError;
+    use lambda_http::{Request, RequestExt};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_lambda_request() {
+        let request = Request::default().with_path_parameters(HashMap::from([
+            ("b".to_owned(), "2".to_owned()),
+            ("a".to_owned(), "100%".to_owned()),
+        ]));
+        assert_eq!(
+            from_lambda_request::<(String, u32)>(&request).unwrap(),
+            ("100%".to_owned(), 2)
+        );
+        assert!(matches!(
+            from_lambda_request::<(u32, u32)>(&request),
+            Err(ParamsDeserializationError::ParseErrorAtIndex { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_no_path_parameters() {
+        assert!(matches!(
+            from_lambda_request::<u32>(&Request::default()),
+            Err(ParamsDeserializationError::WrongNumberOfParameters {
+                got: 0,
+                expected: 1
+            })
+        ));
+    }
+}
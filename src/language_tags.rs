@@ -0,0 +1,83 @@
+//! `#[serde(with)]` helpers for BCP 47 [`LanguageTag`] params, like `/{lang}/docs`.
+//!
+//! Tags are validated against the IANA registry and canonicalized, so `en-Latn-US` becomes
+//! `en-US` and deprecated subtags are replaced.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use language_tags::LanguageTag;
+//!
+//! #[derive(Deserialize)]
+//! struct Docs {
+//!     #[serde(with = "matchit_serde::language_tags")]
+//!     lang: LanguageTag,
+//! }
+//!
+//! let docs: Docs = matchit_serde::from_slice(&[("lang", "en-Latn-US")]).unwrap();
+//! assert_eq!(docs.lang.as_str(), "en-US");
+//! ```
+
+use crate::parse::ParseVisitor;
+use ::language_tags::LanguageTag;
+use core::marker::PhantomData;
+use serde::{Deserializer, Serializer};
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<LanguageTag, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |value| {
+            let tag = LanguageTag::parse(value).ok()?.canonicalize().ok()?;
+            tag.is_valid().then_some(tag)
+        },
+        expecting: "a valid BCP 47 language tag",
+        marker: PhantomData,
+    })
+}
+
+pub fn serialize<S>(value: &LanguageTag, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(value.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use language_tags::LanguageTag;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Docs {
+        #[serde(with = "super")]
+        lang: LanguageTag,
+    }
+
+    #[test]
+    fn test_language_tags() {
+        for (value, canonical) in [
+            ("de-CH", "de-CH"),
+            ("en-Latn-US", "en-US"),
+            ("iw", "he"),
+            ("zh-Hant-TW", "zh-Hant-TW"),
+        ] {
+            let docs: Docs = from_slice(&[("lang", value)]).unwrap();
+            assert_eq!(docs.lang.as_str(), canonical);
+            assert_eq!(
+                to_pairs(&docs).unwrap(),
+                [("lang".to_owned(), canonical.to_owned())]
+            );
+        }
+
+        for value in ["english", "xx-YY", ""] {
+            let err = from_slice::<Docs>(&[("lang", value)]).unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                    if key == "lang" && expected_type.ends_with("LanguageTag")
+            ));
+        }
+    }
+}
@@ -1,9 +1,150 @@
-use serde::de::{
-    self, Visitor,
-    value::{MapDeserializer, SeqDeserializer},
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{any::type_name, str::Utf8Error};
+use serde::{
+    de::{
+        self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess,
+        Visitor,
+    },
+    ser,
 };
-use std::{any::type_name, borrow::Cow, str::Utf8Error};
-use thiserror::Error;
+
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "aide")]
+mod aide;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "base-x")]
+pub mod base_x;
+#[cfg(feature = "std")]
+pub mod blocking;
+mod bounded;
+#[cfg(feature = "bson")]
+pub mod bson;
+#[cfg(feature = "bytesize")]
+pub mod bytesize;
+#[cfg(feature = "camino")]
+pub mod camino;
+mod config;
+pub mod decimal_comma;
+pub mod duration;
+#[cfg(feature = "either")]
+pub mod either;
+mod explain;
+mod from_str;
+#[cfg(feature = "hex")]
+pub mod hex;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "humantime")]
+pub mod humantime;
+#[cfg(feature = "hyper")]
+pub mod hyper;
+#[cfg(feature = "isocountry")]
+pub mod isocountry;
+#[cfg(feature = "jiff")]
+pub mod jiff;
+#[cfg(feature = "lambda")]
+mod lambda;
+#[cfg(feature = "lambda_http")]
+mod lambda_http;
+#[cfg(feature = "language-tags")]
+pub mod language_tags;
+#[cfg(feature = "log")]
+pub mod log;
+mod matrix;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "mime")]
+pub mod mime;
+#[cfg(feature = "ntex")]
+pub mod ntex;
+#[cfg(feature = "num-bigint")]
+pub mod num_bigint;
+mod pairs;
+mod parse;
+#[cfg(feature = "poem")]
+pub mod poem;
+pub mod presence;
+#[cfg(feature = "query")]
+mod query;
+mod redact;
+mod reserved;
+#[cfg(feature = "salvo")]
+pub mod salvo;
+pub mod saturating;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "semver")]
+pub mod semver;
+pub mod shared_str;
+mod sources;
+#[cfg(feature = "sqids")]
+pub mod sqids;
+#[cfg(any(feature = "proptest", all(test, feature = "std")))]
+pub mod strategy;
+#[cfg(feature = "utoipa")]
+mod template;
+#[cfg(all(
+    test,
+    any(
+        feature = "axum",
+        feature = "hyper",
+        feature = "ntex",
+        feature = "poem",
+        feature = "tower"
+    )
+))]
+mod test_util;
+#[cfg(feature = "std")]
+pub mod testing;
+#[cfg(feature = "time")]
+pub mod time;
+#[cfg(feature = "tower")]
+pub mod tower;
+#[cfg(feature = "ulid")]
+pub mod ulid;
+#[cfg(feature = "url")]
+pub mod url;
+#[cfg(feature = "utoipa")]
+pub mod utoipa;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+#[cfg(feature = "warp")]
+pub mod warp;
+#[cfg(feature = "worker")]
+mod worker;
+
+pub use bounded::{Bounded, NonEmptyString};
+pub use config::{Config, Constraint};
+pub use explain::{Explanation, FieldReport, explain};
+pub use from_str::FromStrParam;
+#[cfg(feature = "http")]
+pub use http::{ExtensionParams, from_headers, get_typed_params, insert_typed_params};
+#[cfg(feature = "lambda")]
+pub use lambda::PathParameters;
+#[cfg(feature = "lambda_http")]
+pub use lambda_http::from_lambda_request;
+pub use matrix::MatrixPath;
+#[cfg(all(feature = "query", feature = "std"))]
+pub use query::from_path_and_query;
+pub use redact::Redacted;
+pub use reserved::{RAW_PATH, TEMPLATE};
+#[cfg(feature = "schemars")]
+pub use schema::schema_for_params;
+pub use sources::{Decoding, Sources};
+#[cfg(feature = "worker")]
+pub use worker::from_route_context;
 
 pub mod macros {
     macro_rules! unsupported_type {
@@ -25,186 +166,1351 @@ pub mod macros {
             where
                 V: serde::de::Visitor<'de>,
             {
-                if self.0.len() != 1 {
+                if self.params.len() != 1 {
                     return Err(ParamsDeserializationError::WrongNumberOfParameters {
-                        got: self.0.len(),
+                        got: self.params.len(),
                         expected: 1,
                     });
                 }
 
-                let value = &self.0.0[0].1;
+                let (key, value) = &self.params.0[0];
                 let value = value
                     .parse()
                     .map_err(|_| ParamsDeserializationError::ParseError {
                         value: value.to_string(),
                         expected_type: $ty,
+                        reason: None,
                     })?;
-                visitor.$visit_fn(value)
+                check_non_zero::<V::Value, _>(&value, Some(key))?;
+                visitor
+                    .$visit_fn(value)
+                    .map_err(|err: ParamsDeserializationError| err.at_key(&self.params.0[0].0))
+            }
+        };
+    }
+    macro_rules! parse_value {
+        ($trait_fn:ident, $visit_fn:ident, $ty:literal) => {
+            fn $trait_fn<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: serde::de::Visitor<'de>,
+            {
+                let value = self
+                    .value
+                    .parse()
+                    .map_err(|_| self.parse_error($ty, None))?;
+                check_non_zero::<V::Value, _>(
+                    &value,
+                    match self.key {
+                        Some(KeyOrIdx::Key(key)) => Some(key),
+                        _ => None,
+                    },
+                )?;
+                visitor
+                    .$visit_fn(value)
+                    .map_err(|err: ParamsDeserializationError| match self.key {
+                        Some(KeyOrIdx::Key(key)) => err.at_key(key),
+                        _ => err,
+                    })
+            }
+        };
+    }
+    macro_rules! forward_to_ref {
+        () => {
+            macros::forward_to_ref! {
+                deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+                deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32
+                deserialize_u64 deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char
+                deserialize_str deserialize_string deserialize_bytes deserialize_byte_buf
+                deserialize_option deserialize_unit deserialize_seq deserialize_map
+                deserialize_identifier deserialize_ignored_any
+            }
+
+            fn deserialize_unit_struct<V>(
+                self,
+                name: &'static str,
+                visitor: V,
+            ) -> Result<V::Value, Self::Error>
+            where
+                V: serde::de::Visitor<'de>,
+            {
+                self.by_ref().deserialize_unit_struct(name, visitor)
+            }
+
+            fn deserialize_newtype_struct<V>(
+                self,
+                name: &'static str,
+                visitor: V,
+            ) -> Result<V::Value, Self::Error>
+            where
+                V: serde::de::Visitor<'de>,
+            {
+                self.by_ref().deserialize_newtype_struct(name, visitor)
+            }
+
+            fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: serde::de::Visitor<'de>,
+            {
+                self.by_ref().deserialize_tuple(len, visitor)
+            }
+
+            fn deserialize_tuple_struct<V>(
+                self,
+                name: &'static str,
+                len: usize,
+                visitor: V,
+            ) -> Result<V::Value, Self::Error>
+            where
+                V: serde::de::Visitor<'de>,
+            {
+                self.by_ref().deserialize_tuple_struct(name, len, visitor)
+            }
+
+            fn deserialize_struct<V>(
+                self,
+                name: &'static str,
+                fields: &'static [&'static str],
+                visitor: V,
+            ) -> Result<V::Value, Self::Error>
+            where
+                V: serde::de::Visitor<'de>,
+            {
+                self.by_ref().deserialize_struct(name, fields, visitor)
+            }
+
+            fn deserialize_enum<V>(
+                self,
+                name: &'static str,
+                variants: &'static [&'static str],
+                visitor: V,
+            ) -> Result<V::Value, Self::Error>
+            where
+                V: serde::de::Visitor<'de>,
+            {
+                self.by_ref().deserialize_enum(name, variants, visitor)
             }
         };
+        ($($trait_fn:ident)*) => {
+            $(
+                fn $trait_fn<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+                where
+                    V: serde::de::Visitor<'de>,
+                {
+                    self.by_ref().$trait_fn(visitor)
+                }
+            )*
+        };
+    }
+
+    /// Builds [`Params`](crate::Params) from `key => value` pairs.
+    ///
+    /// The values are used as they are, without percent-decoding.
+    ///
+    /// ```
+    /// let params = matchit_serde::params! { "id" => "3", "name" => "x" };
+    /// assert_eq!(params.get("name"), Some("x"));
+    /// ```
+    #[macro_export]
+    macro_rules! params {
+        ($($key:expr => $value:expr),* $(,)?) => {
+            <$crate::Params as ::core::iter::FromIterator<_>>::from_iter([$(($key, $value)),*])
+        };
     }
+
+    pub(crate) use forward_to_ref;
     pub(crate) use parse_single_value;
+    pub(crate) use parse_value;
     pub(crate) use unsupported_type;
 }
 
+/// Percent-decoded parameters of a matched route.
 #[derive(Debug, Clone)]
-pub struct Params<'de>(Vec<(&'de str, Cow<'de, str>)>);
+pub struct Params<'de>(Vec<(Cow<'de, str>, Cow<'de, str>)>);
 
+#[cfg(feature = "std")]
 impl<'de> TryFrom<&'de matchit::Params<'de, 'de>> for Params<'de> {
     type Error = ParamsDeserializationError;
 
     fn try_from(params: &'de matchit::Params) -> Result<Self, ParamsDeserializationError> {
+        Self::decode(params.iter())
+    }
+}
+
+impl<'de> Params<'de> {
+    fn decode(
+        params: impl Iterator<Item = (&'de str, &'de str)>,
+    ) -> Result<Self, ParamsDeserializationError> {
         Ok(Self(
             params
-                .iter()
-                .map(|(k, v)| Ok((k, percent_encoding::percent_decode_str(v).decode_utf8()?)))
+                .map(|(k, v)| {
+                    Ok((
+                        Cow::Borrowed(k),
+                        percent_encoding::percent_decode_str(v).decode_utf8()?,
+                    ))
+                })
                 .collect::<Result<Vec<_>, Utf8Error>>()?,
         ))
     }
-}
 
-impl<'de> Params<'de> {
-    fn len(&self) -> usize {
+    /// Borrows params which are already decoded, like the path parameters of API Gateway,
+    /// sorted by key since the map they come from has no meaningful order.
+    #[cfg(any(feature = "lambda", feature = "lambda_http"))]
+    fn from_decoded_map(params: impl Iterator<Item = (&'de str, &'de str)>) -> Self {
+        let mut params: Vec<_> = params
+            .map(|(k, v)| (Cow::Borrowed(k), Cow::Borrowed(v)))
+            .collect();
+        params.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        Self(params)
+    }
+
+    /// Adds the full path of the request under [`RAW_PATH`], for targets with a field renamed to
+    /// it. Other targets never see it.
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// use matchit_serde::{Params, ParamsDeserializer};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     id: u32,
+    ///     #[serde(rename = "$raw_path")]
+    ///     path: String,
+    /// }
+    ///
+    /// let mut router = matchit::Router::new();
+    /// router.insert("/users/{id}", ()).unwrap();
+    /// let matched = router.at("/users/7").unwrap();
+    /// let params = Params::try_from(&matched.params).unwrap().with_raw_path("/users/7");
+    /// let deserializer = ParamsDeserializer::new(params);
+    /// assert_eq!(deserializer.deserialize::<User>().unwrap().path, "/users/7");
+    /// assert_eq!(deserializer.deserialize::<u32>().unwrap(), 7);
+    /// ```
+    pub fn with_raw_path(mut self, path: &'de str) -> Self {
+        self.0.push((Cow::Borrowed(RAW_PATH), Cow::Borrowed(path)));
+        self
+    }
+
+    /// Returns the number of params.
+    pub fn len(&self) -> usize {
         self.0.len()
     }
 
-    fn iter_entries(&'de self) -> impl Iterator<Item = (&'de str, &'de str)> {
-        self.0.iter().map(|(k, v)| (*k, v.as_ref()))
+    /// Returns `true` if there are no params.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the decoded value of the first param with the given key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_ref())
+    }
+
+    /// Returns the decoded value of the first param with the given key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no param with the given key.
+    #[track_caller]
+    pub fn require(&self, key: &str) -> &str {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!(
+                "no param with key {key:?}, available keys: {:?}",
+                self.0.iter().map(|(k, _)| k.as_ref()).collect::<Vec<_>>()
+            ),
+        }
+    }
+
+    /// Returns an iterator over the keys and decoded values of the params.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&str, &str)> + ExactSizeIterator {
+        self.0.iter().map(|(k, v)| (k.as_ref(), v.as_ref()))
+    }
+
+    /// Merges the params of `other` into `self`, resolving keys present in both according to
+    /// `policy`.
+    ///
+    /// ```
+    /// use matchit_serde::{Conflict, params};
+    ///
+    /// let mut params = params! { "tenant" => "acme", "id" => "1" };
+    /// params.merge(params! { "id" => "2" }, Conflict::Replace).unwrap();
+    /// assert_eq!(params.get("id"), Some("2"));
+    /// ```
+    pub fn merge(
+        &mut self,
+        other: Params<'de>,
+        policy: Conflict,
+    ) -> Result<(), ParamsDeserializationError> {
+        if policy == Conflict::Error
+            && let Some((key, _)) = other.0.iter().find(|(key, _)| self.get(key).is_some())
+        {
+            return Err(ParamsDeserializationError::ConflictingKey(key.to_string()));
+        }
+
+        let existing = self.0.len();
+        for (key, value) in other.0 {
+            let position = self.0[..existing].iter().position(|(k, _)| *k == key);
+            match (position, policy) {
+                (None, _) | (Some(_), Conflict::KeepBoth) => self.0.push((key, value)),
+                (Some(_), Conflict::Keep | Conflict::Error) => {}
+                (Some(idx), Conflict::Replace) => self.0[idx].1 = value,
+            }
+        }
+        Ok(())
+    }
+
+    /// Collects the params into a [`BTreeMap`](alloc::collections::BTreeMap) borrowing the keys
+    /// and values.
+    ///
+    /// If a key occurs multiple times, the first value is kept, like with [`Params::get`].
+    pub fn to_btree_map(&self) -> alloc::collections::BTreeMap<&str, &str> {
+        self.iter().rev().collect()
+    }
+
+    /// Converts the params into a [`BTreeMap`](alloc::collections::BTreeMap) of owned keys and
+    /// values.
+    ///
+    /// If a key occurs multiple times, the first value is kept, like with [`Params::get`].
+    pub fn into_btree_map(self) -> alloc::collections::BTreeMap<String, String> {
+        self.0
+            .into_iter()
+            .rev()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect()
+    }
+
+    /// Collects the params into a [`HashMap`](std::collections::HashMap) borrowing the keys and
+    /// values.
+    ///
+    /// If a key occurs multiple times, the first value is kept, like with [`Params::get`].
+    #[cfg(feature = "std")]
+    pub fn to_hash_map(&self) -> std::collections::HashMap<&str, &str> {
+        self.iter().rev().collect()
+    }
+
+    /// Converts the params into a [`HashMap`](std::collections::HashMap) of owned keys and
+    /// values.
+    ///
+    /// If a key occurs multiple times, the first value is kept, like with [`Params::get`].
+    #[cfg(feature = "std")]
+    pub fn into_hash_map(self) -> std::collections::HashMap<String, String> {
+        self.0
+            .into_iter()
+            .rev()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect()
+    }
+
+    /// Returns a view of the params which masks the values of the `sensitive` keys when
+    /// formatted with `Debug` or `Display`.
+    pub fn redacted<'a>(&'a self, sensitive: &'a [&'a str]) -> Redacted<'a, 'de> {
+        Redacted::new(self, sensitive)
+    }
+
+    /// Copies the params into an owned [`ParamsBuf`].
+    pub fn to_buf(&self) -> ParamsBuf {
+        ParamsBuf(
+            self.0
+                .iter()
+                .map(|(k, v)| (k.as_ref().to_owned(), v.as_ref().to_owned()))
+                .collect(),
+        )
+    }
+
+    /// Converts the params into an owned [`ParamsBuf`], reusing already owned values.
+    pub fn into_buf(self) -> ParamsBuf {
+        ParamsBuf(
+            self.0
+                .into_iter()
+                .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                .collect(),
+        )
+    }
+}
+
+impl From<Params<'_>> for ParamsBuf {
+    fn from(params: Params<'_>) -> Self {
+        params.into_buf()
+    }
+}
+
+/// How [`Params::merge`] resolves a key that is present in both sets of params.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+    /// Keep the existing value.
+    Keep,
+    /// Replace the existing value with the new one.
+    Replace,
+    /// Keep both values. Deserializing a struct field from such a key fails with a duplicate
+    /// field error.
+    KeepBoth,
+    /// Fail with [`ParamsDeserializationError::ConflictingKey`] without merging anything.
+    Error,
+}
+
+/// Appends already decoded key/value pairs without checking for conflicts.
+impl<'de> Extend<(&'de str, &'de str)> for Params<'de> {
+    fn extend<I: IntoIterator<Item = (&'de str, &'de str)>>(&mut self, iter: I) {
+        self.0.extend(
+            iter.into_iter()
+                .map(|(k, v)| (Cow::Borrowed(k), Cow::Borrowed(v))),
+        );
+    }
+}
+
+/// Equivalent to [`Params::require`].
+impl core::ops::Index<&str> for Params<'_> {
+    type Output = str;
+
+    #[track_caller]
+    fn index(&self, key: &str) -> &str {
+        self.require(key)
+    }
+}
+
+/// Creates params from already decoded key/value pairs.
+impl<'de> From<&[(&'de str, &'de str)]> for Params<'de> {
+    fn from(params: &[(&'de str, &'de str)]) -> Self {
+        params.iter().copied().collect()
+    }
+}
+
+/// Creates params from already decoded key/value pairs.
+impl<'de> FromIterator<(&'de str, &'de str)> for Params<'de> {
+    fn from_iter<I: IntoIterator<Item = (&'de str, &'de str)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(k, v)| (Cow::Borrowed(k), Cow::Borrowed(v)))
+                .collect(),
+        )
+    }
+}
+
+/// Serializes the params as a map of keys to decoded values.
+impl ser::Serialize for Params<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+/// Owned counterpart of [`Params`].
+///
+/// Unlike [`Params`] it doesn't borrow from the router or the matched path, so it can be held
+/// across `.await` points or sent to other tasks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParamsBuf(Vec<(String, String)>);
+
+impl ParamsBuf {
+    /// Borrows the params as [`Params`].
+    pub fn as_params(&self) -> Params<'_> {
+        Params(
+            self.0
+                .iter()
+                .map(|(k, v)| (Cow::Borrowed(k.as_str()), Cow::Borrowed(v.as_str())))
+                .collect(),
+        )
+    }
+
+    /// Owned counterpart of [`Params::with_raw_path`].
+    pub fn with_raw_path(mut self, path: impl Into<String>) -> Self {
+        self.0.push((RAW_PATH.into(), path.into()));
+        self
+    }
+
+    /// Creates a [`ParamsDeserializer`] borrowing from the params.
+    pub fn deserializer(&self) -> ParamsDeserializer<'_> {
+        ParamsDeserializer::new(self.as_params())
+    }
+
+    /// Deserializes `T` from the params.
+    pub fn deserialize<'de, T>(&'de self) -> Result<T, ParamsDeserializationError>
+    where
+        T: de::Deserialize<'de>,
+    {
+        self.deserializer().deserialize()
+    }
+}
+
+/// Serializes the params as a map of keys to decoded values.
+impl ser::Serialize for ParamsBuf {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+    }
+}
+
+impl From<Vec<(String, String)>> for ParamsBuf {
+    fn from(params: Vec<(String, String)>) -> Self {
+        Self(params)
     }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<&matchit::Params<'_, '_>> for ParamsBuf {
+    type Error = ParamsDeserializationError;
 
-    fn values(&'de self) -> impl Iterator<Item = &'de str> {
-        self.0.iter().map(|(_k, v)| v.as_ref())
+    fn try_from(params: &matchit::Params) -> Result<Self, ParamsDeserializationError> {
+        Ok(Params::decode(params.iter())?.into_buf())
     }
 }
 
-#[derive(Debug, Clone, Error)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "thiserror", derive(thiserror::Error))]
 pub enum ParamsDeserializationError {
-    #[error("Unsupported type: {0}")]
+    #[cfg_attr(feature = "thiserror", error("Unsupported type: {0}"))]
     UnsupportedType(&'static str),
-    #[error("{0}")]
+    #[cfg_attr(feature = "thiserror", error("{0}"))]
     Custom(String),
-    #[error("Wrong number of parameters. Expected {expected}, got {got}")]
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Wrong number of parameters. Expected {expected}, got {got}")
+    )]
     WrongNumberOfParameters { got: usize, expected: usize },
-    #[error("Failed to parse value \"{value}\" as type {expected_type}")]
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Failed to parse value \"{value}\" as type {expected_type}{}", .reason.as_ref().map(|reason| alloc::format!(": {reason}")).unwrap_or_default())
+    )]
     ParseError {
         value: String,
         expected_type: &'static str,
+        reason: Option<String>,
     },
-    #[error("Failed to parse value \"{value}\" at key \"{key}\" as type {expected_type}")]
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Failed to parse value \"{value}\" at key \"{key}\" as type {expected_type}{}", .reason.as_ref().map(|reason| alloc::format!(": {reason}")).unwrap_or_default())
+    )]
     ParseErrorAtKey {
         key: String,
         value: String,
         expected_type: &'static str,
+        reason: Option<String>,
     },
 
-    #[error("Failed to parse value \"{value}\" at index \"{index}\" as type {expected_type}")]
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Failed to parse value \"{value}\" at index \"{index}\" as type {expected_type}{}", .reason.as_ref().map(|reason| alloc::format!(": {reason}")).unwrap_or_default())
+    )]
     ParseErrorAtIndex {
         index: usize,
         value: String,
         expected_type: &'static str,
+        reason: Option<String>,
+    },
+
+    #[cfg_attr(feature = "thiserror", error(transparent))]
+    Utf8Error(#[cfg_attr(feature = "thiserror", from)] Utf8Error),
+
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Conflicting values for parameter \"{0}\"")
+    )]
+    ConflictingKey(String),
+
+    #[cfg_attr(feature = "thiserror", error("No matched params found"))]
+    MissingParams,
+
+    #[cfg_attr(feature = "thiserror", error("NaN is not a valid value"))]
+    NotANumber,
+
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Invalid length {got}{}, expected {expected}", .key.as_ref().map(|key| alloc::format!(" at key \"{key}\"")).unwrap_or_default())
+    )]
+    InvalidLength {
+        key: Option<String>,
+        got: usize,
+        expected: String,
+    },
+
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Invalid character '{character}'{}, expected {expected}", .key.as_ref().map(|key| alloc::format!(" at key \"{key}\"")).unwrap_or_default())
+    )]
+    InvalidCharacter {
+        key: Option<String>,
+        character: char,
+        expected: String,
+    },
+
+    #[cfg(feature = "validator")]
+    #[cfg_attr(feature = "thiserror", error("Validation failed: {0}"))]
+    Validation(#[cfg_attr(feature = "thiserror", from)] validator::ValidationErrors),
+
+    #[cfg(feature = "garde")]
+    #[cfg_attr(feature = "thiserror", error("Validation failed: {0}"))]
+    Garde(#[cfg_attr(feature = "thiserror", from)] garde::Report),
+
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Value at key \"{key}\" violates constraint: {constraint}")
+    )]
+    ConstraintViolation { key: String, constraint: String },
+
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Value at key \"{key}\" is {len} bytes long, longer than the maximum of {max}")
+    )]
+    ValueTooLong { key: String, len: usize, max: usize },
+
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Too many parameters. Got {got}, the maximum is {max}")
+    )]
+    TooManyParams { got: usize, max: usize },
+
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Parameters are {len} bytes long, longer than the maximum of {max}")
+    )]
+    ParamsTooLarge { len: usize, max: usize },
+
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Value{} must be non-zero", .key.as_ref().map(|key| alloc::format!(" at key \"{key}\"")).unwrap_or_default())
+    )]
+    MustBeNonZero { key: Option<String> },
+
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Expected exactly one character{}, got \"{value}\"", .key.as_ref().map(|key| alloc::format!(" at key \"{key}\"")).unwrap_or_default())
+    )]
+    ExpectedOneCharacter { key: Option<String>, value: String },
+
+    #[cfg_attr(
+        feature = "thiserror",
+        error("Unknown variant \"{variant}\"{}, expected {expected}", .key.as_ref().map(|key| alloc::format!(" at key \"{key}\"")).unwrap_or_default())
+    )]
+    UnknownVariant {
+        key: Option<String>,
+        variant: String,
+        expected: String,
     },
+}
+
+#[cfg(not(feature = "thiserror"))]
+impl core::fmt::Display for ParamsDeserializationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnsupportedType(name) => write!(f, "Unsupported type: {name}"),
+            Self::Custom(msg) => write!(f, "{msg}"),
+            Self::WrongNumberOfParameters { got, expected } => {
+                write!(
+                    f,
+                    "Wrong number of parameters. Expected {expected}, got {got}"
+                )
+            }
+            Self::ParseError {
+                value,
+                expected_type,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Failed to parse value \"{value}\" as type {expected_type}"
+                )?;
+                write_reason(f, reason)
+            }
+            Self::ParseErrorAtKey {
+                key,
+                value,
+                expected_type,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Failed to parse value \"{value}\" at key \"{key}\" as type {expected_type}"
+                )?;
+                write_reason(f, reason)
+            }
+            Self::ParseErrorAtIndex {
+                index,
+                value,
+                expected_type,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Failed to parse value \"{value}\" at index \"{index}\" as type {expected_type}"
+                )?;
+                write_reason(f, reason)
+            }
+            Self::Utf8Error(err) => core::fmt::Display::fmt(err, f),
+            Self::ConflictingKey(key) => write!(f, "Conflicting values for parameter \"{key}\""),
+            Self::MissingParams => write!(f, "No matched params found"),
+            Self::NotANumber => write!(f, "NaN is not a valid value"),
+            Self::InvalidLength {
+                key: Some(key),
+                got,
+                expected,
+            } => write!(
+                f,
+                "Invalid length {got} at key \"{key}\", expected {expected}"
+            ),
+            Self::InvalidLength {
+                key: None,
+                got,
+                expected,
+            } => write!(f, "Invalid length {got}, expected {expected}"),
+            Self::InvalidCharacter {
+                key: Some(key),
+                character,
+                expected,
+            } => write!(
+                f,
+                "Invalid character '{character}' at key \"{key}\", expected {expected}"
+            ),
+            Self::InvalidCharacter {
+                key: None,
+                character,
+                expected,
+            } => write!(f, "Invalid character '{character}', expected {expected}"),
+            #[cfg(feature = "validator")]
+            Self::Validation(errors) => write!(f, "Validation failed: {errors}"),
+            #[cfg(feature = "garde")]
+            Self::Garde(report) => write!(f, "Validation failed: {report}"),
+            Self::ConstraintViolation { key, constraint } => {
+                write!(
+                    f,
+                    "Value at key \"{key}\" violates constraint: {constraint}"
+                )
+            }
+            Self::ValueTooLong { key, len, max } => {
+                write!(
+                    f,
+                    "Value at key \"{key}\" is {len} bytes long, longer than the maximum of {max}"
+                )
+            }
+            Self::TooManyParams { got, max } => {
+                write!(f, "Too many parameters. Got {got}, the maximum is {max}")
+            }
+            Self::ParamsTooLarge { len, max } => {
+                write!(
+                    f,
+                    "Parameters are {len} bytes long, longer than the maximum of {max}"
+                )
+            }
+            Self::MustBeNonZero { key: Some(key) } => {
+                write!(f, "Value at key \"{key}\" must be non-zero")
+            }
+            Self::MustBeNonZero { key: None } => write!(f, "Value must be non-zero"),
+            Self::ExpectedOneCharacter {
+                key: Some(key),
+                value,
+            } => write!(
+                f,
+                "Expected exactly one character at key \"{key}\", got \"{value}\""
+            ),
+            Self::ExpectedOneCharacter { key: None, value } => {
+                write!(f, "Expected exactly one character, got \"{value}\"")
+            }
+            Self::UnknownVariant {
+                key: Some(key),
+                variant,
+                expected,
+            } => write!(
+                f,
+                "Unknown variant \"{variant}\" at key \"{key}\", expected {expected}"
+            ),
+            Self::UnknownVariant {
+                key: None,
+                variant,
+                expected,
+            } => write!(f, "Unknown variant \"{variant}\", expected {expected}"),
+        }
+    }
+}
+
+#[cfg(not(feature = "thiserror"))]
+fn write_reason(f: &mut core::fmt::Formatter<'_>, reason: &Option<String>) -> core::fmt::Result {
+    match reason {
+        Some(reason) => write!(f, ": {reason}"),
+        None => Ok(()),
+    }
+}
+
+#[cfg(not(feature = "thiserror"))]
+impl core::error::Error for ParamsDeserializationError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Utf8Error(err) => err.source(),
+            #[cfg(feature = "validator")]
+            Self::Validation(errors) => Some(errors),
+            #[cfg(feature = "garde")]
+            Self::Garde(report) => Some(report),
+            _ => None,
+        }
+    }
+}
 
-    #[error(transparent)]
-    Utf8Error(#[from] Utf8Error),
+#[cfg(not(feature = "thiserror"))]
+impl From<Utf8Error> for ParamsDeserializationError {
+    fn from(err: Utf8Error) -> Self {
+        Self::Utf8Error(err)
+    }
+}
+
+#[cfg(all(feature = "validator", not(feature = "thiserror")))]
+impl From<validator::ValidationErrors> for ParamsDeserializationError {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        Self::Validation(errors)
+    }
+}
+
+#[cfg(all(feature = "garde", not(feature = "thiserror")))]
+impl From<garde::Report> for ParamsDeserializationError {
+    fn from(report: garde::Report) -> Self {
+        Self::Garde(report)
+    }
 }
 
 impl ParamsDeserializationError {
-    fn unsupported_type<T>() -> Self {
+    pub(crate) fn unsupported_type<T>() -> Self {
         Self::UnsupportedType(type_name::<T>())
     }
+
+    /// Returns the name of the variant in snake case, like `parse_error_at_key`, for logs and
+    /// metrics labels.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::UnsupportedType(_) => "unsupported_type",
+            Self::Custom(_) => "custom",
+            Self::WrongNumberOfParameters { .. } => "wrong_number_of_parameters",
+            Self::ParseError { .. } => "parse_error",
+            Self::ParseErrorAtKey { .. } => "parse_error_at_key",
+            Self::ParseErrorAtIndex { .. } => "parse_error_at_index",
+            Self::Utf8Error(_) => "utf8_error",
+            Self::ConflictingKey(_) => "conflicting_key",
+            Self::MissingParams => "missing_params",
+            Self::NotANumber => "not_a_number",
+            Self::InvalidLength { .. } => "invalid_length",
+            Self::InvalidCharacter { .. } => "invalid_character",
+            #[cfg(feature = "validator")]
+            Self::Validation(_) => "validation",
+            #[cfg(feature = "garde")]
+            Self::Garde(_) => "garde",
+            Self::ConstraintViolation { .. } => "constraint_violation",
+            Self::ValueTooLong { .. } => "value_too_long",
+            Self::TooManyParams { .. } => "too_many_params",
+            Self::ParamsTooLarge { .. } => "params_too_large",
+            Self::MustBeNonZero { .. } => "must_be_non_zero",
+            Self::ExpectedOneCharacter { .. } => "expected_one_character",
+            Self::UnknownVariant { .. } => "unknown_variant",
+        }
+    }
+
+    /// Names `key` in errors that are raised without knowing it.
+    fn at_key(self, key: &str) -> Self {
+        match self {
+            Self::MustBeNonZero { key: None } => Self::MustBeNonZero {
+                key: Some(key.to_string()),
+            },
+            Self::InvalidLength {
+                key: None,
+                got,
+                expected,
+            } => Self::InvalidLength {
+                key: Some(key.to_string()),
+                got,
+                expected,
+            },
+            Self::InvalidCharacter {
+                key: None,
+                character,
+                expected,
+            } => Self::InvalidCharacter {
+                key: Some(key.to_string()),
+                character,
+                expected,
+            },
+            Self::UnknownVariant {
+                key: None,
+                variant,
+                expected,
+            } => Self::UnknownVariant {
+                key: Some(key.to_string()),
+                variant,
+                expected,
+            },
+            err => err,
+        }
+    }
+
+    /// Returns the key of the param the error is about, if known.
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Self::ParseErrorAtKey { key, .. }
+            | Self::ConstraintViolation { key, .. }
+            | Self::ValueTooLong { key, .. }
+            | Self::ConflictingKey(key) => Some(key),
+            Self::MustBeNonZero { key }
+            | Self::ExpectedOneCharacter { key, .. }
+            | Self::InvalidLength { key, .. }
+            | Self::InvalidCharacter { key, .. }
+            | Self::UnknownVariant { key, .. } => key.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the error is a bug in the app rather than in the request, which is the
+    /// case for [`MissingParams`](Self::MissingParams) only.
+    ///
+    /// The web framework adapters respond with `500 Internal Server Error` to these and with
+    /// `400 Bad Request` to all other errors.
+    pub fn is_server_error(&self) -> bool {
+        matches!(self, Self::MissingParams)
+    }
 }
 
 impl de::Error for ParamsDeserializationError {
     fn custom<T>(msg: T) -> Self
     where
-        T: std::fmt::Display,
+        T: core::fmt::Display,
     {
         Self::Custom(msg.to_string())
     }
+
+    fn invalid_value(unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        match unexp {
+            // Raised by e.g. `ordered_float::NotNan` after a successful float parse.
+            de::Unexpected::Float(value) if value.is_nan() => Self::NotANumber,
+            de::Unexpected::Char(character) => Self::InvalidCharacter {
+                key: None,
+                character,
+                expected: exp.to_string(),
+            },
+            _ => Self::custom(format_args!("invalid value: {unexp}, expected {exp}")),
+        }
+    }
+
+    fn invalid_length(len: usize, exp: &dyn de::Expected) -> Self {
+        Self::InvalidLength {
+            key: None,
+            got: len,
+            expected: exp.to_string(),
+        }
+    }
+
+    fn unknown_variant(variant: &str, expected: &'static [&'static str]) -> Self {
+        let expected = match expected {
+            [] => "no variants".to_string(),
+            [only] => alloc::format!("`{only}`"),
+            expected => alloc::format!(
+                "one of {}",
+                expected
+                    .iter()
+                    .map(|variant| alloc::format!("`{variant}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        Self::UnknownVariant {
+            key: None,
+            variant: variant.to_string(),
+            expected,
+        }
+    }
 }
 
+/// Deserializer over a set of [`Params`].
+///
+/// The [`Deserializer`](de::Deserializer) is implemented both for `ParamsDeserializer` and for
+/// `&ParamsDeserializer`. The latter is `Copy`, so the same instance can be used to deserialize
+/// any number of target types.
+///
+/// Values aren't limited in length, use a [`Config`] to cap them.
+#[derive(Debug, Clone)]
 pub struct ParamsDeserializer<'de>(Params<'de>);
 
 impl<'de> ParamsDeserializer<'de> {
     pub fn new(params: Params<'de>) -> Self {
         Self(params)
     }
-}
-
-impl<'de> de::Deserializer<'de> for &ParamsDeserializer<'de> {
-    type Error = ParamsDeserializationError;
-
-    macros::unsupported_type!(deserialize_bytes);
-    macros::unsupported_type!(deserialize_option);
-    macros::unsupported_type!(deserialize_identifier);
-    macros::unsupported_type!(deserialize_ignored_any);
-    macros::unsupported_type!(deserialize_str);
-    macros::unsupported_type!(deserialize_any);
 
-    macros::parse_single_value!(deserialize_bool, visit_bool, "bool");
-    macros::parse_single_value!(deserialize_i8, visit_i8, "i8");
-    macros::parse_single_value!(deserialize_i16, visit_i16, "i16");
-    macros::parse_single_value!(deserialize_i32, visit_i32, "i32");
-    macros::parse_single_value!(deserialize_i64, visit_i64, "i64");
-    macros::parse_single_value!(deserialize_i128, visit_i128, "i128");
-    macros::parse_single_value!(deserialize_u8, visit_u8, "u8");
-    macros::parse_single_value!(deserialize_u16, visit_u16, "u16");
-    macros::parse_single_value!(deserialize_u32, visit_u32, "u32");
-    macros::parse_single_value!(deserialize_u64, visit_u64, "u64");
-    macros::parse_single_value!(deserialize_u128, visit_u128, "u128");
-    macros::parse_single_value!(deserialize_f32, visit_f32, "f32");
-    macros::parse_single_value!(deserialize_f64, visit_f64, "f64");
-    macros::parse_single_value!(deserialize_string, visit_string, "String");
-    macros::parse_single_value!(deserialize_byte_buf, visit_string, "String");
-    macros::parse_single_value!(deserialize_char, visit_char, "char");
+    /// Returns a borrowed view of the deserializer.
+    pub fn by_ref(&self) -> ParamsRefDeserializer<'_, 'de> {
+        ParamsRefDeserializer { params: &self.0 }
+    }
 
-    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    /// Deserializes `T` from the params without consuming the deserializer.
+    pub fn deserialize<T>(&self) -> Result<T, ParamsDeserializationError>
     where
-        V: Visitor<'de>,
+        T: de::Deserialize<'de>,
     {
-        visitor.visit_unit()
+        self.deserialize_at::<T, _>(None, |deserializer| T::deserialize(deserializer))
     }
 
-    fn deserialize_unit_struct<V>(
-        self,
-        _name: &'static str,
-        visitor: V,
-    ) -> Result<V::Value, Self::Error>
+    /// Like [`deserialize`](Self::deserialize), for the params of a route matching `template`.
+    ///
+    /// The template is recorded by the `tracing` instrumentation, and passed to a field of `T`
+    /// renamed to [`TEMPLATE`].
+    pub fn deserialize_route<T>(&self, template: &str) -> Result<T, ParamsDeserializationError>
     where
-        V: Visitor<'de>,
+        T: de::Deserialize<'de>,
     {
-        visitor.visit_unit()
+        self.deserialize_at::<T, _>(Some(template), |deserializer| T::deserialize(deserializer))
     }
 
-    fn deserialize_newtype_struct<V>(
-        self,
-        _name: &'static str,
-        visitor: V,
-    ) -> Result<V::Value, Self::Error>
+    /// Deserializes `T` and returns the params none of its fields consumed as well, so they can
+    /// be logged or forwarded instead of silently disappearing.
+    ///
+    /// Only the fields of a struct leave params unconsumed. Any other `T`, like a map or a
+    /// struct with a flattened field, consumes all of them.
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// use matchit_serde::{ParamsDeserializer, params};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     id: u32,
+    /// }
+    ///
+    /// let deserializer = ParamsDeserializer::new(params! { "id" => "1", "tab" => "posts" });
+    /// let (user, unused) = deserializer.deserialize_with_unused::<User>().unwrap();
+    /// assert_eq!(user.id, 1);
+    /// assert_eq!(unused.get("tab"), Some("posts"));
+    /// ```
+    pub fn deserialize_with_unused<T>(&self) -> Result<(T, Params<'de>), ParamsDeserializationError>
     where
-        V: Visitor<'de>,
+        T: de::Deserialize<'de>,
     {
-        visitor.visit_newtype_struct(self)
+        self.deserialize_at::<T, _>(None, |deserializer| {
+            let (result, trace) = explain::trace::<T>(&deserializer.0);
+            let unused = deserializer
+                .0
+                .0
+                .iter()
+                .filter(|(key, _)| trace.unused.iter().any(|unused| unused == key))
+                .cloned()
+                .collect();
+            Ok((result?, Params(unused)))
+        })
+    }
+
+    /// Deserializes `A`, then `B` from the params `A` didn't consume, see
+    /// [`deserialize_with_unused`](Self::deserialize_with_unused). Useful for layered
+    /// extractors, like the params of an auth scope and the ones of the resource.
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// use matchit_serde::{ParamsDeserializer, params};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Scope {
+    ///     org: String,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Resource {
+    ///     kind: String,
+    ///     id: u32,
+    /// }
+    ///
+    /// let deserializer =
+    ///     ParamsDeserializer::new(params! { "org" => "acme", "kind" => "repo", "id" => "7" });
+    /// let (scope, resource) = deserializer.deserialize_split::<Scope, Resource>().unwrap();
+    /// assert_eq!(scope.org, "acme");
+    /// assert_eq!((resource.kind.as_str(), resource.id), ("repo", 7));
+    /// ```
+    pub fn deserialize_split<A, B>(&self) -> Result<(A, B), ParamsDeserializationError>
+    where
+        A: de::Deserialize<'de>,
+        B: de::Deserialize<'de>,
+    {
+        let (a, unused) = self.deserialize_with_unused::<A>()?;
+        let b = ParamsDeserializer::new(unused).deserialize()?;
+        Ok((a, b))
+    }
+
+    /// Runs `deserialize` for the target `T` on the params prepared for it, recording failures by
+    /// the enabled diagnostics features.
+    fn deserialize_at<T, R>(
+        &self,
+        template: Option<&str>,
+        deserialize: impl FnOnce(&ParamsDeserializer<'de>) -> Result<R, ParamsDeserializationError>,
+    ) -> Result<R, ParamsDeserializationError>
+    where
+        T: de::Deserialize<'de>,
+    {
+        // Values may be sensitive, so diagnostics only contain the keys.
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "deserialize_params",
+            template,
+            target = type_name::<T>(),
+            params = %Redacted::all(&self.0),
+        )
+        .entered();
+        let prepared = reserved::prepare::<T>(&self.0, template).map(ParamsDeserializer::new);
+        let result = deserialize(prepared.as_ref().unwrap_or(self));
+        #[cfg(any(feature = "tracing", feature = "log", feature = "metrics"))]
+        if let Err(err) = &result {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                kind = err.kind(),
+                key = err.key(),
+                "failed to deserialize params"
+            );
+            #[cfg(feature = "log")]
+            log::failure::<T>(template, &self.0, err);
+            #[cfg(feature = "metrics")]
+            metrics::failure(template, err);
+        }
+        result
+    }
+
+    /// Deserializes `T` from the params and runs its `validator` checks, failing with
+    /// [`ParamsDeserializationError::Validation`].
+    #[cfg(feature = "validator")]
+    pub fn deserialize_validated<T>(&self) -> Result<T, ParamsDeserializationError>
+    where
+        T: de::Deserialize<'de> + validator::Validate,
+    {
+        let value: T = self.deserialize()?;
+        value.validate()?;
+        Ok(value)
+    }
+
+    /// Deserializes `T` from the params and runs its `garde` checks with the default context,
+    /// failing with [`ParamsDeserializationError::Garde`].
+    #[cfg(feature = "garde")]
+    pub fn deserialize_garde<T>(&self) -> Result<T, ParamsDeserializationError>
+    where
+        T: de::Deserialize<'de> + garde::Validate,
+        T::Context: Default,
+    {
+        self.deserialize_garde_with(&T::Context::default())
+    }
+
+    /// Like [`deserialize_garde`](Self::deserialize_garde), but with the validation context `ctx`.
+    #[cfg(feature = "garde")]
+    pub fn deserialize_garde_with<T>(
+        &self,
+        ctx: &T::Context,
+    ) -> Result<T, ParamsDeserializationError>
+    where
+        T: de::Deserialize<'de> + garde::Validate,
+    {
+        let value: T = self.deserialize()?;
+        value.validate_with(ctx)?;
+        Ok(value)
+    }
+}
+
+/// Borrowed view of a [`ParamsDeserializer`], also obtained through
+/// [`IntoDeserializer`] on `&Params`.
+#[derive(Debug, Clone, Copy)]
+pub struct ParamsRefDeserializer<'a, 'de> {
+    params: &'a Params<'de>,
+}
+
+/// Deserializes `T` from the params of a matched route.
+///
+/// ```
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct User {
+///     id: u32,
+/// }
+///
+/// let mut router = matchit::Router::new();
+/// router.insert("/users/{id}", ()).unwrap();
+/// let matched = router.at("/users/42").unwrap();
+///
+/// let user: User = matchit_serde::from_params(&matched.params).unwrap();
+/// assert_eq!(user.id, 42);
+/// ```
+#[cfg(feature = "std")]
+pub fn from_params<'de, T>(
+    params: &'de matchit::Params<'de, 'de>,
+) -> Result<T, ParamsDeserializationError>
+where
+    T: de::Deserialize<'de>,
+{
+    ParamsDeserializer::new(Params::try_from(params)?).deserialize()
+}
+
+/// Deserializes `T` from raw key/value pairs.
+///
+/// The values are percent-decoded, just like the ones passed to [`from_params`].
+pub fn from_slice<'de, T>(
+    params: &'de [(&'de str, &'de str)],
+) -> Result<T, ParamsDeserializationError>
+where
+    T: de::Deserialize<'de>,
+{
+    ParamsDeserializer::new(Params::decode(params.iter().copied())?).deserialize()
+}
+
+/// Deserializes `T` from the params, filling fields which are missing from the params with the
+/// ones of `defaults`.
+///
+/// `defaults` is serialized into key/value pairs first, so its fields have to be scalar values.
+/// `None` fields of `defaults` are treated as missing.
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// use matchit_serde::params;
+///
+/// #[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Listing {
+///     page: u32,
+///     per_page: u32,
+/// }
+///
+/// let defaults = Listing { page: 1, per_page: 20 };
+/// let listing = matchit_serde::deserialize_with_defaults(params! { "page" => "3" }, &defaults);
+/// assert_eq!(listing.unwrap(), Listing { page: 3, per_page: 20 });
+/// ```
+pub fn deserialize_with_defaults<'de, T>(
+    mut params: Params<'de>,
+    defaults: &T,
+) -> Result<T, ParamsDeserializationError>
+where
+    T: de::Deserialize<'de> + ser::Serialize,
+{
+    let defaults = pairs::to_pairs(defaults)?
+        .into_iter()
+        .map(|(k, v)| (Cow::Owned(k), Cow::Owned(v)))
+        .collect();
+    params.merge(Params(defaults), Conflict::Keep)?;
+    ParamsDeserializer::new(params).deserialize()
+}
+
+/// Transcodes the params into `serializer` as a map of keys to string values, for example to
+/// write them to a JSON or CBOR audit log.
+#[cfg(feature = "transcode")]
+pub fn transcode_params<S>(params: &Params<'_>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    serde_transcode::transcode(params.into_deserializer(), serializer)
+}
+
+impl<'de> de::Deserializer<'de> for ParamsRefDeserializer<'_, 'de> {
+    type Error = ParamsDeserializationError;
+
+    macros::unsupported_type!(deserialize_bytes);
+    macros::unsupported_type!(deserialize_option);
+    macros::unsupported_type!(deserialize_identifier);
+    macros::unsupported_type!(deserialize_ignored_any);
+
+    macros::parse_single_value!(deserialize_bool, visit_bool, "bool");
+    macros::parse_single_value!(deserialize_i8, visit_i8, "i8");
+    macros::parse_single_value!(deserialize_i16, visit_i16, "i16");
+    macros::parse_single_value!(deserialize_i32, visit_i32, "i32");
+    macros::parse_single_value!(deserialize_i64, visit_i64, "i64");
+    macros::parse_single_value!(deserialize_i128, visit_i128, "i128");
+    macros::parse_single_value!(deserialize_u8, visit_u8, "u8");
+    macros::parse_single_value!(deserialize_u16, visit_u16, "u16");
+    macros::parse_single_value!(deserialize_u32, visit_u32, "u32");
+    macros::parse_single_value!(deserialize_u64, visit_u64, "u64");
+    macros::parse_single_value!(deserialize_u128, visit_u128, "u128");
+    macros::parse_single_value!(deserialize_f32, visit_f32, "f32");
+    macros::parse_single_value!(deserialize_f64, visit_f64, "f64");
+    macros::parse_single_value!(deserialize_string, visit_string, "String");
+    macros::parse_single_value!(deserialize_byte_buf, visit_string, "String");
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.params.len() != 1 {
+            return Err(ParamsDeserializationError::WrongNumberOfParameters {
+                got: self.params.len(),
+                expected: 1,
+            });
+        }
+
+        let (key, value) = &self.params.0[0];
+        ValueDeserializer {
+            key: Some(KeyOrIdx::Key(key)),
+            value,
+        }
+        .deserialize_char(visitor)
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.params.len() != 1 {
+            return Err(ParamsDeserializationError::WrongNumberOfParameters {
+                got: self.params.len(),
+                expected: 1,
+            });
+        }
+
+        let (key, value) = &self.params.0[0];
+        ValueDeserializer {
+            key: Some(KeyOrIdx::Key(key)),
+            value,
+        }
+        .deserialize_str(visitor)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_seq(SeqDeserializer::new(self.0.values()))
+        visitor.visit_seq(ParamsSeqAccess::new(self.params))
     }
 
     fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        if self.0.len() != len {
+        if self.params.len() != len {
             return Err(Self::Error::WrongNumberOfParameters {
-                got: self.0.len(),
+                got: self.params.len(),
                 expected: len,
             });
         }
-        visitor.visit_seq(SeqDeserializer::new(self.0.values()))
+        visitor.visit_seq(ParamsSeqAccess::new(self.params))
     }
 
     fn deserialize_tuple_struct<V>(
@@ -216,33 +1522,33 @@ impl<'de> de::Deserializer<'de> for &ParamsDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        if self.0.len() != len {
+        if self.params.len() != len {
             return Err(Self::Error::WrongNumberOfParameters {
-                got: self.0.len(),
+                got: self.params.len(),
                 expected: len,
             });
         }
 
-        visitor.visit_seq(SeqDeserializer::new(self.0.values()))
+        visitor.visit_seq(ParamsSeqAccess::new(self.params))
     }
 
     fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_map(MapDeserializer::new(self.0.iter_entries()))
+        visitor.visit_map(ParamsMapAccess::new(self.params))
     }
 
     fn deserialize_struct<V>(
         self,
         _name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.deserialize_map(visitor)
+        visitor.visit_map(StructAccess::new(self.params, fields))
     }
 
     fn deserialize_enum<V>(
@@ -258,49 +1564,1820 @@ impl<'de> de::Deserializer<'de> for &ParamsDeserializer<'de> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use matchit::Match;
-    use serde::Deserialize;
+impl<'de> de::Deserializer<'de> for &ParamsDeserializer<'de> {
+    type Error = ParamsDeserializationError;
 
-    #[test]
-    fn test() {
-        let mut router = matchit::Router::new();
-        router.insert("/{principal}/{path}", ()).unwrap();
-        let Match { params, .. } = router.at("/user/interesting").unwrap();
+    macros::forward_to_ref!();
+}
 
-        let params = Params::try_from(&params).unwrap();
-        let deserializer = ParamsDeserializer::new(params);
+impl<'de> de::Deserializer<'de> for ParamsDeserializer<'de> {
+    type Error = ParamsDeserializationError;
 
-        #[derive(Debug, Clone, Deserialize, PartialEq)]
-        struct Path {
-            principal: String,
-            path: String,
+    macros::forward_to_ref!();
+}
+
+impl<'de> IntoDeserializer<'de, ParamsDeserializationError> for ParamsDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> IntoDeserializer<'de, ParamsDeserializationError> for Params<'de> {
+    type Deserializer = ParamsDeserializer<'de>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ParamsDeserializer::new(self)
+    }
+}
+
+impl<'de> IntoDeserializer<'de, ParamsDeserializationError> for &ParamsDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> IntoDeserializer<'de, ParamsDeserializationError> for ParamsRefDeserializer<'_, 'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'a, 'de> IntoDeserializer<'de, ParamsDeserializationError> for &'a Params<'de> {
+    type Deserializer = ParamsRefDeserializer<'a, 'de>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ParamsRefDeserializer { params: self }
+    }
+}
+
+struct ParamsSeqAccess<'a, 'de> {
+    params: core::iter::Enumerate<core::slice::Iter<'a, (Cow<'de, str>, Cow<'de, str>)>>,
+}
+
+impl<'a, 'de> ParamsSeqAccess<'a, 'de> {
+    fn new(params: &'a Params<'de>) -> Self {
+        Self {
+            params: params.0.iter().enumerate(),
         }
+    }
+}
 
-        let path = Path::deserialize(&deserializer).unwrap();
-        assert_eq!(
-            path,
-            Path {
-                principal: "user".to_owned(),
-                path: "interesting".to_owned()
+impl<'de> SeqAccess<'de> for ParamsSeqAccess<'_, 'de> {
+    type Error = ParamsDeserializationError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.params.next() {
+            Some((idx, (_key, value))) => seed
+                .deserialize(ValueDeserializer {
+                    key: Some(KeyOrIdx::Idx(idx)),
+                    value,
+                })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.params.len())
+    }
+}
+
+struct ParamsMapAccess<'a, 'de> {
+    params: core::slice::Iter<'a, (Cow<'de, str>, Cow<'de, str>)>,
+    value: Option<(&'a str, &'a Cow<'de, str>)>,
+}
+
+impl<'a, 'de> ParamsMapAccess<'a, 'de> {
+    fn new(params: &'a Params<'de>) -> Self {
+        Self {
+            params: params.0.iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'a, 'de> MapAccess<'de> for ParamsMapAccess<'a, 'de> {
+    type Error = ParamsDeserializationError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.params.next() {
+            Some((key, value)) => {
+                self.value = Some((key, value));
+                seed.deserialize(KeyDeserializer(key)).map(Some)
             }
-        );
+            None => Ok(None),
+        }
+    }
 
-        let path = <(String, String)>::deserialize(&deserializer).unwrap();
-        assert_eq!(path, ("user".to_owned(), "interesting".to_owned()));
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, value) = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(ValueDeserializer {
+            key: Some(KeyOrIdx::Key(key)),
+            value,
+        })
     }
 
-    #[test]
-    fn test_urldecode() {
-        let mut router = matchit::Router::new();
-        router.insert("/{principal}/{path}", ()).unwrap();
-        let Match { params, .. } = router.at("/user/%28interesting%29").unwrap();
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.params.len())
+    }
+}
 
-        let params = Params::try_from(&params).unwrap();
-        let deserializer = ParamsDeserializer::new(params);
-        let path = <(String, String)>::deserialize(&deserializer).unwrap();
-        assert_eq!(path, ("user".to_owned(), "(interesting)".to_owned()));
+/// Map access for structs, visiting params in the order of the struct's fields.
+///
+/// Above [`INDEX_THRESHOLD`] params, keys are looked up through an index sorted by key, so
+/// resolving a field is a binary search instead of a scan over all params. Smaller sets are
+/// scanned, which doesn't allocate. Params that don't belong to any field are visited last, so
+/// `deny_unknown_fields` and similar checks still see them.
+struct StructAccess<'a, 'de> {
+    params: &'a Params<'de>,
+    fields: core::slice::Iter<'static, &'static str>,
+    field: &'static str,
+    sorted: Option<Vec<usize>>,
+    consumed: Consumed,
+    matches: core::ops::Range<usize>,
+    leftover: usize,
+    value: Option<usize>,
+}
+
+/// Number of params up to which [`StructAccess`] scans them instead of building an index.
+const INDEX_THRESHOLD: usize = 16;
+
+/// The params a [`StructAccess`] has visited, as a bit set while they fit into one word.
+enum Consumed {
+    Bits(u64),
+    Flags(Vec<bool>),
+}
+
+impl Consumed {
+    fn new(len: usize) -> Self {
+        if len <= u64::BITS as usize {
+            Self::Bits(0)
+        } else {
+            Self::Flags(alloc::vec![false; len])
+        }
+    }
+
+    fn contains(&self, idx: usize) -> bool {
+        match self {
+            Self::Bits(bits) => bits & (1 << idx) != 0,
+            Self::Flags(flags) => flags[idx],
+        }
+    }
+
+    fn insert(&mut self, idx: usize) {
+        match self {
+            Self::Bits(bits) => *bits |= 1 << idx,
+            Self::Flags(flags) => flags[idx] = true,
+        }
+    }
+}
+
+impl<'a, 'de> StructAccess<'a, 'de> {
+    fn new(params: &'a Params<'de>, fields: &'static [&'static str]) -> Self {
+        let sorted = (params.len() > INDEX_THRESHOLD).then(|| {
+            let mut sorted: Vec<usize> = (0..params.len()).collect();
+            sorted.sort_by_key(|&idx| &params.0[idx].0);
+            sorted
+        });
+        Self {
+            params,
+            fields: fields.iter(),
+            field: "",
+            sorted,
+            consumed: Consumed::new(params.len()),
+            matches: 0..0,
+            leftover: 0,
+            value: None,
+        }
+    }
+
+    fn key(&self, idx: usize) -> &'a str {
+        &self.params.0[idx].0
+    }
+
+    /// Returns the positions to check for `field`: its range in the index, or all params.
+    fn lookup(&self, field: &str) -> core::ops::Range<usize> {
+        let Some(sorted) = &self.sorted else {
+            return 0..self.params.len();
+        };
+        let start = sorted.partition_point(|&idx| self.key(idx) < field);
+        let len = sorted[start..].partition_point(|&idx| self.key(idx) == field);
+        start..start + len
+    }
+
+    fn next_param(&mut self) -> Option<usize> {
+        loop {
+            while let Some(pos) = self.matches.next() {
+                match &self.sorted {
+                    Some(sorted) => return Some(sorted[pos]),
+                    None if self.key(pos) == self.field => return Some(pos),
+                    None => {}
+                }
+            }
+            self.field = self.fields.next()?;
+            self.matches = self.lookup(self.field);
+        }
+    }
+
+    fn next_leftover(&mut self) -> Option<usize> {
+        while self.leftover < self.params.len() {
+            let idx = self.leftover;
+            self.leftover += 1;
+            if !self.consumed.contains(idx) {
+                return Some(idx);
+            }
+        }
+        None
+    }
+}
+
+impl<'a, 'de> MapAccess<'de> for StructAccess<'a, 'de> {
+    type Error = ParamsDeserializationError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let Some(idx) = self.next_param().or_else(|| self.next_leftover()) else {
+            return Ok(None);
+        };
+        self.consumed.insert(idx);
+        self.value = Some(idx);
+        seed.deserialize(KeyDeserializer(&self.params.0[idx].0))
+            .map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let idx = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        let params: &'a Params<'de> = self.params;
+        let (key, value) = &params.0[idx];
+        seed.deserialize(ValueDeserializer {
+            key: Some(KeyOrIdx::Key(key)),
+            value,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(
+            (0..self.params.len())
+                .filter(|&idx| !self.consumed.contains(idx))
+                .count(),
+        )
+    }
+}
+
+struct KeyDeserializer<'a, 'de>(&'a Cow<'de, str>);
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer<'_, 'de> {
+    type Error = ParamsDeserializationError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Cow::Borrowed(key) => visitor.visit_borrowed_str(key),
+            Cow::Owned(key) => visitor.visit_str(key),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum KeyOrIdx<'a> {
+    Key(&'a str),
+    Idx(usize),
+}
+
+/// Rejects a zero `value` if it is parsed for one of the `NonZero*` integers, whose own error
+/// doesn't tell it apart from other invalid values.
+fn check_non_zero<T, N>(value: &N, key: Option<&str>) -> Result<(), ParamsDeserializationError>
+where
+    N: Default + PartialEq,
+{
+    use core::num::{
+        NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize, NonZeroU8,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    };
+
+    let non_zero = [
+        type_name::<NonZeroI8>(),
+        type_name::<NonZeroI16>(),
+        type_name::<NonZeroI32>(),
+        type_name::<NonZeroI64>(),
+        type_name::<NonZeroI128>(),
+        type_name::<NonZeroIsize>(),
+        type_name::<NonZeroU8>(),
+        type_name::<NonZeroU16>(),
+        type_name::<NonZeroU32>(),
+        type_name::<NonZeroU64>(),
+        type_name::<NonZeroU128>(),
+        type_name::<NonZeroUsize>(),
+    ];
+    if *value == N::default() && non_zero.contains(&type_name::<T>()) {
+        return Err(ParamsDeserializationError::MustBeNonZero {
+            key: key.map(ToString::to_string),
+        });
+    }
+    Ok(())
+}
+
+struct ValueDeserializer<'a, 'de> {
+    key: Option<KeyOrIdx<'a>>,
+    value: &'a Cow<'de, str>,
+}
+
+impl ValueDeserializer<'_, '_> {
+    fn parse_error(
+        &self,
+        expected_type: &'static str,
+        reason: Option<String>,
+    ) -> ParamsDeserializationError {
+        let value = self.value.to_string();
+        match self.key {
+            Some(KeyOrIdx::Key(key)) => ParamsDeserializationError::ParseErrorAtKey {
+                key: key.to_string(),
+                value,
+                expected_type,
+                reason,
+            },
+            Some(KeyOrIdx::Idx(index)) => ParamsDeserializationError::ParseErrorAtIndex {
+                index,
+                value,
+                expected_type,
+                reason,
+            },
+            None => ParamsDeserializationError::ParseError {
+                value,
+                expected_type,
+                reason,
+            },
+        }
+    }
+
+    /// Turns custom errors of a visitor parsing the value, like the ones of `uuid` or `chrono`,
+    /// into a parse error naming the key and the expected type with their message as reason, and
+    /// names the key in other errors raised without it.
+    fn visit_error<T>(&self, err: ParamsDeserializationError) -> ParamsDeserializationError {
+        match (err, self.key) {
+            (ParamsDeserializationError::Custom(reason), _) => {
+                self.parse_error(type_name::<T>(), Some(reason))
+            }
+            (err, Some(KeyOrIdx::Key(key))) => err.at_key(key),
+            (err, _) => err,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'_, 'de> {
+    type Error = ParamsDeserializationError;
+
+    macros::unsupported_type!(deserialize_map);
+    macros::unsupported_type!(deserialize_seq);
+
+    macros::parse_value!(deserialize_bool, visit_bool, "bool");
+    macros::parse_value!(deserialize_i8, visit_i8, "i8");
+    macros::parse_value!(deserialize_i16, visit_i16, "i16");
+    macros::parse_value!(deserialize_i32, visit_i32, "i32");
+    macros::parse_value!(deserialize_i64, visit_i64, "i64");
+    macros::parse_value!(deserialize_i128, visit_i128, "i128");
+    macros::parse_value!(deserialize_u8, visit_u8, "u8");
+    macros::parse_value!(deserialize_u16, visit_u16, "u16");
+    macros::parse_value!(deserialize_u32, visit_u32, "u32");
+    macros::parse_value!(deserialize_u64, visit_u64, "u64");
+    macros::parse_value!(deserialize_u128, visit_u128, "u128");
+    macros::parse_value!(deserialize_f32, visit_f32, "f32");
+    macros::parse_value!(deserialize_f64, visit_f64, "f64");
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let mut chars = self.value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(character), None) => visitor.visit_char(character),
+            _ => Err(ParamsDeserializationError::ExpectedOneCharacter {
+                key: match self.key {
+                    Some(KeyOrIdx::Key(key)) => Some(key.to_string()),
+                    _ => None,
+                },
+                value: self.value.to_string(),
+            }),
+        }
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Cow::Borrowed(value) => visitor.visit_borrowed_str(value),
+            Cow::Owned(value) => visitor.visit_str(value),
+        }
+        .map_err(|err| self.visit_error::<V::Value>(err))
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Cow::Borrowed(value) => visitor.visit_borrowed_bytes(value.as_bytes()),
+            Cow::Owned(value) => visitor.visit_bytes(value.as_bytes()),
+        }
+        .map_err(|err| self.visit_error::<V::Value>(err))
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(ParamsDeserializationError::unsupported_type::<V::Value>())
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(ParamsDeserializationError::unsupported_type::<V::Value>())
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(ParamsDeserializationError::unsupported_type::<V::Value>())
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Identifiers, like enum variants, aren't parsed into a value type, so their errors are
+        // kept as they are instead of becoming parse errors naming the identifier type.
+        match self.value {
+            Cow::Borrowed(value) => visitor.visit_borrowed_str(value),
+            Cow::Owned(value) => visitor.visit_str(value),
+        }
+        .map_err(|err: ParamsDeserializationError| match self.key {
+            Some(KeyOrIdx::Key(key)) => err.at_key(key),
+            _ => err,
+        })
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
+
+impl<'de> EnumAccess<'de> for ValueDeserializer<'_, 'de> {
+    type Error = ParamsDeserializationError;
+    type Variant = UnitVariant;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self).map(|variant| (variant, UnitVariant))
+    }
+}
+
+struct UnitVariant;
+
+impl<'de> VariantAccess<'de> for UnitVariant {
+    type Error = ParamsDeserializationError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Err(ParamsDeserializationError::UnsupportedType(
+            "newtype enum variant",
+        ))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(ParamsDeserializationError::UnsupportedType(
+            "tuple enum variant",
+        ))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(ParamsDeserializationError::UnsupportedType(
+            "struct enum variant",
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use matchit::Match;
+    use serde::Deserialize;
+
+    #[test]
+    fn test() {
+        let mut router = matchit::Router::new();
+        router.insert("/{principal}/{path}", ()).unwrap();
+        let Match { params, .. } = router.at("/user/interesting").unwrap();
+
+        let params = Params::try_from(&params).unwrap();
+        let deserializer = ParamsDeserializer::new(params);
+
+        #[derive(Debug, Clone, Deserialize, PartialEq)]
+        struct Path {
+            principal: String,
+            path: String,
+        }
+
+        let path = Path::deserialize(&deserializer).unwrap();
+        assert_eq!(
+            path,
+            Path {
+                principal: "user".to_owned(),
+                path: "interesting".to_owned()
+            }
+        );
+
+        let path = <(String, String)>::deserialize(&deserializer).unwrap();
+        assert_eq!(path, ("user".to_owned(), "interesting".to_owned()));
+    }
+
+    #[test]
+    fn test_urldecode() {
+        let mut router = matchit::Router::new();
+        router.insert("/{principal}/{path}", ()).unwrap();
+        let Match { params, .. } = router.at("/user/%28interesting%29").unwrap();
+
+        let params = Params::try_from(&params).unwrap();
+        let deserializer = ParamsDeserializer::new(params);
+        let path = <(String, String)>::deserialize(&deserializer).unwrap();
+        assert_eq!(path, ("user".to_owned(), "(interesting)".to_owned()));
+    }
+
+    #[test]
+    fn test_raw_path() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(deny_unknown_fields)]
+        struct File {
+            name: String,
+            #[serde(rename = "$raw_path")]
+            path: String,
+        }
+
+        let mut router = matchit::Router::new();
+        router.insert("/files/{name}", ()).unwrap();
+        let path = "/files/a%20b";
+        let Match { params, .. } = router.at(path).unwrap();
+
+        let params = Params::try_from(&params).unwrap().with_raw_path(path);
+        let deserializer = ParamsDeserializer::new(params);
+        assert_eq!(
+            deserializer.deserialize::<File>().unwrap(),
+            File {
+                name: "a b".to_owned(),
+                path: "/files/a%20b".to_owned(),
+            }
+        );
+        assert_eq!(deserializer.deserialize::<String>().unwrap(), "a b");
+        assert_eq!(deserializer.deserialize::<(String,)>().unwrap().0, "a b");
+    }
+
+    #[test]
+    fn test_template() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(deny_unknown_fields)]
+        struct Post {
+            post: u32,
+            #[serde(rename = "$template")]
+            route: String,
+        }
+
+        let deserializer = ParamsDeserializer::new(params! { "post" => "3" });
+        assert_eq!(
+            deserializer
+                .deserialize_route::<Post>("/posts/{post}")
+                .unwrap(),
+            Post {
+                post: 3,
+                route: "/posts/{post}".to_owned(),
+            }
+        );
+        assert_eq!(
+            deserializer
+                .deserialize_route::<u32>("/posts/{post}")
+                .unwrap(),
+            3
+        );
+        assert!(matches!(
+            deserializer.deserialize::<Post>(),
+            Err(ParamsDeserializationError::Custom(_))
+        ));
+    }
+
+    #[test]
+    fn test_reuse_deserializer() {
+        let mut router = matchit::Router::new();
+        router.insert("/{principal}/{path}", ()).unwrap();
+        let Match { params, .. } = router.at("/user/interesting").unwrap();
+
+        let params = Params::try_from(&params).unwrap();
+        let deserializer = ParamsDeserializer::new(params);
+
+        let tuple: (String, String) = deserializer.deserialize().unwrap();
+        let list: Vec<String> = deserializer.deserialize().unwrap();
+        assert_eq!(tuple, ("user".to_owned(), "interesting".to_owned()));
+        assert_eq!(list, vec!["user".to_owned(), "interesting".to_owned()]);
+
+        let by_ref = &deserializer;
+        let copy = by_ref;
+        assert!(<(String, String)>::deserialize(by_ref).is_ok());
+        assert!(<(String, String)>::deserialize(copy).is_ok());
+    }
+
+    #[test]
+    fn test_wide_struct() {
+        let mut router = matchit::Router::new();
+        router
+            .insert("/{f}/{e}/{d}/{c}/{b}/{a}/{extra}", ())
+            .unwrap();
+        let Match { params, .. } = router.at("/6/5/4/3/2/1/x").unwrap();
+
+        let params = Params::try_from(&params).unwrap();
+        let deserializer = ParamsDeserializer::new(params);
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Wide {
+            a: u8,
+            b: u16,
+            c: u32,
+            d: u64,
+            e: i32,
+            f: String,
+        }
+
+        let wide: Wide = deserializer.deserialize().unwrap();
+        assert_eq!(
+            wide,
+            Wide {
+                a: 1,
+                b: 2,
+                c: 3,
+                d: 4,
+                e: 5,
+                f: "6".to_owned()
+            }
+        );
+
+        #[derive(Debug, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        #[allow(dead_code)]
+        struct Strict {
+            a: u8,
+        }
+        assert!(deserializer.deserialize::<Strict>().is_err());
+    }
+
+    #[test]
+    fn test_indexed_struct() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Item {
+            a: u8,
+            z: String,
+        }
+
+        let keys: Vec<String> = (0..INDEX_THRESHOLD).map(|i| format!("p{i}")).collect();
+        let mut pairs: Vec<(&str, &str)> = keys.iter().map(|key| (key.as_str(), "x")).collect();
+        pairs.extend([("z", "last"), ("a", "1")]);
+        assert_eq!(
+            from_slice::<Item>(&pairs).unwrap(),
+            Item {
+                a: 1,
+                z: "last".to_owned()
+            }
+        );
+
+        #[derive(Debug, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        #[allow(dead_code)]
+        struct Strict {
+            a: u8,
+            z: String,
+        }
+        let err = from_slice::<Strict>(&pairs).unwrap_err();
+        assert!(err.to_string().contains("p0"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_error_at_key() {
+        let mut router = matchit::Router::new();
+        router.insert("/{id}", ()).unwrap();
+        let Match { params, .. } = router.at("/abc").unwrap();
+
+        let params = Params::try_from(&params).unwrap();
+        let deserializer = ParamsDeserializer::new(params);
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Id {
+            id: u32,
+        }
+
+        assert!(matches!(
+            deserializer.deserialize::<Id>(),
+            Err(ParamsDeserializationError::ParseErrorAtKey { key, expected_type: "u32", .. }) if key == "id"
+        ));
+        assert!(matches!(
+            deserializer.deserialize::<(u32,)>(),
+            Err(ParamsDeserializationError::ParseErrorAtIndex { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_variant() {
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Kind {
+            Post,
+            Page,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Item {
+            kind: Kind,
+        }
+
+        let deserializer = ParamsDeserializer::new(params! { "kind" => "user" });
+        let err = deserializer.deserialize::<Item>().unwrap_err();
+        assert_eq!(err.kind(), "unknown_variant");
+        assert_eq!(err.key(), Some("kind"));
+        assert_eq!(
+            err.to_string(),
+            "Unknown variant \"user\" at key \"kind\", expected one of `post`, `page`"
+        );
+
+        let err = deserializer.deserialize::<(Kind,)>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown variant \"user\", expected one of `post`, `page`"
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = ParamsDeserializationError::ParseErrorAtKey {
+            key: "id".to_owned(),
+            value: "abc".to_owned(),
+            expected_type: "u32",
+            reason: None,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse value \"abc\" at key \"id\" as type u32"
+        );
+
+        let err = ParamsDeserializationError::ParseErrorAtIndex {
+            index: 0,
+            value: "abc".to_owned(),
+            expected_type: "Uuid",
+            reason: Some("invalid length".to_owned()),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse value \"abc\" at index \"0\" as type Uuid: invalid length"
+        );
+
+        let err = ParamsDeserializationError::WrongNumberOfParameters {
+            got: 2,
+            expected: 1,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Wrong number of parameters. Expected 1, got 2"
+        );
+    }
+
+    #[test]
+    fn test_from_slice() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Post<'a> {
+            id: u32,
+            slug: &'a str,
+            title: String,
+        }
+
+        let post: Post = from_slice(&[("title", "a%20b"), ("slug", "hello"), ("id", "7")]).unwrap();
+        assert_eq!(
+            post,
+            Post {
+                id: 7,
+                slug: "hello",
+                title: "a b".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn test_into_buf() {
+        let mut router = matchit::Router::new();
+        router.insert("/{principal}/{path}", ()).unwrap();
+        let Match { params, .. } = router.at("/user/%28interesting%29").unwrap();
+
+        let params = Params::try_from(&params).unwrap();
+        let owned = params.to_buf();
+        assert_eq!(owned, params.clone().into_buf());
+
+        let borrowed = ParamsDeserializer::new(params)
+            .deserialize::<(String, String)>()
+            .unwrap();
+        assert_eq!(borrowed, owned.deserialize::<(String, String)>().unwrap());
+        assert_eq!(owned.as_params().into_buf(), owned);
+    }
+
+    #[test]
+    fn test_params_buf() {
+        let mut router = matchit::Router::new();
+        router.insert("/{principal}/{path}", ()).unwrap();
+        let params = {
+            let path = "/user/%28interesting%29".to_owned();
+            let Match { params, .. } = router.at(&path).unwrap();
+            ParamsBuf::try_from(&params).unwrap()
+        };
+
+        let path = std::thread::spawn(move || params.deserialize::<(String, String)>().unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(path, ("user".to_owned(), "(interesting)".to_owned()));
+    }
+
+    #[test]
+    fn test_into_deserializer() {
+        fn parse<'de, T, D>(params: D) -> T
+        where
+            T: Deserialize<'de>,
+            D: IntoDeserializer<'de, ParamsDeserializationError>,
+        {
+            T::deserialize(params.into_deserializer()).unwrap()
+        }
+
+        let mut router = matchit::Router::new();
+        router.insert("/{principal}/{path}", ()).unwrap();
+        let Match { params, .. } = router.at("/user/interesting").unwrap();
+
+        let params = Params::try_from(&params).unwrap();
+        let expected = ("user".to_owned(), "interesting".to_owned());
+        assert_eq!(parse::<(String, String), _>(&params), expected);
+
+        let deserializer = ParamsDeserializer::new(params.clone());
+        assert_eq!(parse::<(String, String), _>(&deserializer), expected);
+        assert_eq!(
+            parse::<(String, String), _>(deserializer.by_ref()),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_deserializer_by_value() {
+        let mut router = matchit::Router::new();
+        router.insert("/{principal}/{path}", ()).unwrap();
+        let Match { params, .. } = router.at("/user/interesting").unwrap();
+
+        let params = Params::try_from(&params).unwrap();
+        let expected = ("user".to_owned(), "interesting".to_owned());
+        let deserializer = ParamsDeserializer::new(params.clone());
+        assert_eq!(
+            <(String, String)>::deserialize(deserializer).unwrap(),
+            expected
+        );
+        assert_eq!(
+            <(String, String)>::deserialize(params.into_deserializer()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_params_accessors() {
+        let mut router = matchit::Router::new();
+        router.insert("/{principal}/{path}", ()).unwrap();
+        let Match { params, .. } = router.at("/user/%28interesting%29").unwrap();
+
+        let params = Params::try_from(&params).unwrap();
+        assert_eq!(params.len(), 2);
+        assert!(!params.is_empty());
+        assert_eq!(params.get("path"), Some("(interesting)"));
+        assert_eq!(params.get("missing"), None);
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("principal", "user"), ("path", "(interesting)")]
+        );
+    }
+
+    #[test]
+    fn test_construct_params() {
+        let from_slice = Params::from(&[("id", "3"), ("name", "x")][..]);
+        let from_iter: Params = [("id", "3"), ("name", "x")].into_iter().collect();
+        let from_macro = params! { "id" => "3", "name" => "x" };
+
+        for params in [from_slice, from_iter, from_macro] {
+            let parsed: (u32, String) = params.into_deserializer().deserialize().unwrap();
+            assert_eq!(parsed, (3, "x".to_owned()));
+        }
+        assert!(params! {}.is_empty());
+    }
+
+    #[test]
+    fn test_serialize_params() {
+        let mut router = matchit::Router::new();
+        router.insert("/{principal}/{path}", ()).unwrap();
+        let Match { params, .. } = router.at("/user/%28interesting%29").unwrap();
+
+        let params = Params::try_from(&params).unwrap();
+        let expected = r#"{"principal":"user","path":"(interesting)"}"#;
+        assert_eq!(serde_json::to_string(&params).unwrap(), expected);
+        assert_eq!(serde_json::to_string(&params.to_buf()).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_index() {
+        let params = params! { "id" => "3", "name" => "x" };
+        assert_eq!(&params["id"], "3");
+        assert_eq!(params.require("name"), "x");
+
+        let err = std::panic::catch_unwind(|| params["missing"].len()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().unwrap(),
+            r#"no param with key "missing", available keys: ["id", "name"]"#
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let parent = params! { "tenant" => "acme", "id" => "1" };
+        let nested = || params! { "id" => "2", "file" => "a.txt" };
+
+        let mut params = parent.clone();
+        params.merge(nested(), Conflict::Keep).unwrap();
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("tenant", "acme"), ("id", "1"), ("file", "a.txt")]
+        );
+
+        let mut params = parent.clone();
+        params.merge(nested(), Conflict::Replace).unwrap();
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("tenant", "acme"), ("id", "2"), ("file", "a.txt")]
+        );
+
+        let mut params = parent.clone();
+        params.merge(nested(), Conflict::KeepBoth).unwrap();
+        assert_eq!(params.len(), 4);
+
+        let mut params = parent.clone();
+        assert!(matches!(
+            params.merge(nested(), Conflict::Error),
+            Err(ParamsDeserializationError::ConflictingKey(key)) if key == "id"
+        ));
+        assert_eq!(params.len(), 2);
+
+        let mut params = parent;
+        params.extend([("file", "b.txt")]);
+        assert_eq!(params.get("file"), Some("b.txt"));
+    }
+
+    #[test]
+    fn test_to_maps() {
+        let mut params = params! { "id" => "3", "name" => "x" };
+        params.extend([("id", "4")]);
+
+        let btree = params.to_btree_map();
+        assert_eq!(btree.len(), 2);
+        assert_eq!(btree["id"], "3");
+        let hash = params.to_hash_map();
+        assert_eq!(hash.len(), 2);
+        assert_eq!(hash["id"], "3");
+
+        assert_eq!(params.clone().into_btree_map()["name"], "x");
+        assert_eq!(params.into_hash_map()["id"], "3");
+    }
+
+    #[test]
+    fn test_deserialize_any() {
+        let params = params! { "id" => "3", "name" => "x" };
+        let value = serde_json::Value::deserialize(params.into_deserializer()).unwrap();
+        assert_eq!(value, serde_json::json!({ "id": "3", "name": "x" }));
+    }
+
+    #[test]
+    fn test_deserialize_with_defaults() {
+        #[derive(Debug, PartialEq, Deserialize, serde::Serialize)]
+        struct Route {
+            tenant: String,
+            id: Option<u32>,
+            format: String,
+        }
+
+        let defaults = Route {
+            tenant: "default".to_owned(),
+            id: None,
+            format: "json".to_owned(),
+        };
+        let route =
+            deserialize_with_defaults(params! { "id" => "7", "tenant" => "acme" }, &defaults)
+                .unwrap();
+        assert_eq!(
+            route,
+            Route {
+                tenant: "acme".to_owned(),
+                id: Some(7),
+                format: "json".to_owned(),
+            }
+        );
+        assert_eq!(
+            deserialize_with_defaults(Params(Vec::new()), &defaults).unwrap(),
+            defaults
+        );
+    }
+
+    #[test]
+    fn test_single_str() {
+        let params = params! { "name" => "alice" };
+        let deserializer = ParamsDeserializer::new(params);
+        assert_eq!(deserializer.deserialize::<&str>().unwrap(), "alice");
+    }
+
+    #[test]
+    fn test_deserialize_with_unused() {
+        #[derive(Debug, Deserialize)]
+        struct Post {
+            user: String,
+            post: u32,
+        }
+
+        let deserializer = ParamsDeserializer::new(
+            params! { "user" => "alice", "lang" => "en", "post" => "1", "tab" => "comments" },
+        );
+        let (post, unused) = deserializer.deserialize_with_unused::<Post>().unwrap();
+        assert_eq!((post.user.as_str(), post.post), ("alice", 1));
+        assert_eq!(
+            unused.iter().collect::<Vec<_>>(),
+            [("lang", "en"), ("tab", "comments")]
+        );
+
+        let (map, unused) = deserializer
+            .deserialize_with_unused::<alloc::collections::BTreeMap<String, String>>()
+            .unwrap();
+        assert_eq!(map.len(), 4);
+        assert!(unused.is_empty());
+
+        let deserializer = ParamsDeserializer::new(params! { "user" => "alice", "post" => "x" });
+        assert!(matches!(
+            deserializer.deserialize_with_unused::<Post>().unwrap_err(),
+            ParamsDeserializationError::ParseErrorAtKey { .. }
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_split() {
+        #[derive(Debug, Deserialize)]
+        struct Scope {
+            org: String,
+            team: Option<String>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Resource {
+            id: u32,
+        }
+
+        let deserializer = ParamsDeserializer::new(params! { "org" => "acme", "id" => "7" });
+        let (scope, resource) = deserializer.deserialize_split::<Scope, Resource>().unwrap();
+        assert_eq!((scope.org.as_str(), scope.team), ("acme", None));
+        assert_eq!(resource.id, 7);
+
+        let deserializer = ParamsDeserializer::new(params! { "org" => "acme", "team" => "a" });
+        assert!(deserializer.deserialize_split::<Scope, Resource>().is_err());
+    }
+
+    #[test]
+    fn test_char() {
+        #[derive(Debug, Deserialize)]
+        struct Initial {
+            initial: char,
+        }
+
+        let initial: Initial = from_slice(&[("initial", "%C3%A9")]).unwrap();
+        assert_eq!(initial.initial, 'é');
+        assert_eq!(from_slice::<char>(&[("c", "%F0%9F%A6%80")]).unwrap(), '🦀');
+
+        let err = from_slice::<Initial>(&[("initial", "ab")]).unwrap_err();
+        assert_eq!(err.key(), Some("initial"));
+        assert_eq!(
+            err.to_string(),
+            "Expected exactly one character at key \"initial\", got \"ab\""
+        );
+        assert!(matches!(
+            from_slice::<(char,)>(&[("c", "")]).unwrap_err(),
+            ParamsDeserializationError::ExpectedOneCharacter { key: None, .. }
+        ));
+    }
+
+    #[test]
+    fn test_non_zero() {
+        use core::num::{NonZeroI64, NonZeroU32};
+
+        #[derive(Debug, Deserialize)]
+        struct Page {
+            page: NonZeroU32,
+            offset: NonZeroI64,
+        }
+
+        let page: Page = from_slice(&[("page", "2"), ("offset", "-1")]).unwrap();
+        assert_eq!(page.page.get(), 2);
+        assert_eq!(page.offset.get(), -1);
+
+        let err = from_slice::<Page>(&[("page", "0"), ("offset", "1")]).unwrap_err();
+        assert_eq!(err.key(), Some("page"));
+        assert_eq!(err.to_string(), "Value at key \"page\" must be non-zero");
+
+        let err = from_slice::<Page>(&[("page", "1"), ("offset", "0")]).unwrap_err();
+        assert_eq!(err.key(), Some("offset"));
+
+        let err = from_slice::<NonZeroU32>(&[("id", "0")]).unwrap_err();
+        assert_eq!(err.key(), Some("id"));
+        assert!(matches!(
+            from_slice::<(Option<NonZeroU32>,)>(&[("id", "0")]).unwrap_err(),
+            ParamsDeserializationError::MustBeNonZero { key: None }
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "transcode"))]
+mod transcode_tests {
+    use super::*;
+
+    #[test]
+    fn test_transcode_params() {
+        let params = params! { "id" => "3", "name" => "x" };
+        let mut out = Vec::new();
+        transcode_params(&params, &mut serde_json::Serializer::new(&mut out)).unwrap();
+        assert_eq!(out, br#"{"id":"3","name":"x"}"#);
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod chrono_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_chrono() {
+        use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+
+        #[derive(Debug, Deserialize)]
+        struct Range {
+            day: NaiveDate,
+            local: NaiveDateTime,
+            from: DateTime<Utc>,
+            to: DateTime<FixedOffset>,
+        }
+
+        let range: Range = ParamsDeserializer::new(params! {
+            "day" => "2024-02-29",
+            "local" => "2024-02-29T12:30:00",
+            "from" => "2024-02-29T12:30:00Z",
+            "to" => "2024-02-29T14:30:00+02:00",
+        })
+        .deserialize()
+        .unwrap();
+        assert_eq!(range.day, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+        assert_eq!(range.local, range.day.and_hms_opt(12, 30, 0).unwrap());
+        assert_eq!(range.from, range.to);
+
+        let err = ParamsDeserializer::new(params! { "day" => "2023-02-29" })
+            .deserialize::<NaiveDate>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, ref value, expected_type, .. }
+                if key == "day" && value == "2023-02-29" && expected_type.ends_with("NaiveDate")
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "rust_decimal"))]
+mod rust_decimal_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_rust_decimal() {
+        use rust_decimal::Decimal;
+
+        #[derive(Debug, Deserialize, serde::Serialize)]
+        struct Price {
+            amount: Decimal,
+        }
+
+        let price: Price = ParamsDeserializer::new(params! { "amount" => "19.90" })
+            .deserialize()
+            .unwrap();
+        assert_eq!(price.amount.scale(), 2);
+        assert_eq!(price.amount.to_string(), "19.90");
+        assert_eq!(
+            pairs::to_pairs(&price).unwrap(),
+            [("amount".to_owned(), "19.90".to_owned())]
+        );
+
+        let err = ParamsDeserializer::new(params! { "amount" => "19,90" })
+            .deserialize::<Price>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey {
+                ref key,
+                ref value,
+                expected_type,
+                reason: Some(ref reason),
+            } if key == "amount"
+                && value == "19,90"
+                && expected_type.ends_with("Decimal")
+                && reason.ends_with("expected a Decimal type representing a fixed-point number")
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "bigdecimal"))]
+mod bigdecimal_tests {
+    use super::*;
+
+    #[test]
+    fn test_bigdecimal() {
+        use bigdecimal::BigDecimal;
+
+        let value = "123456789012345678901234567890.000000000000000000001";
+        let (amount,): (BigDecimal,) = ParamsDeserializer::new(params! { "amount" => value })
+            .deserialize()
+            .unwrap();
+        assert_eq!(amount.to_string(), value);
+
+        let err = ParamsDeserializer::new(params! { "amount" => "1e" })
+            .deserialize::<(BigDecimal,)>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtIndex { index: 0, expected_type, .. }
+                if expected_type.ends_with("BigDecimal")
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "ordered-float"))]
+mod ordered_float_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_ordered_float() {
+        use ordered_float::{NotNan, OrderedFloat};
+
+        #[derive(Debug, Deserialize)]
+        struct Point {
+            x: OrderedFloat<f64>,
+            y: NotNan<f32>,
+        }
+
+        let point: Point = ParamsDeserializer::new(params! { "x" => "NaN", "y" => "1.5" })
+            .deserialize()
+            .unwrap();
+        assert!(point.x.is_nan());
+        assert_eq!(point.y, NotNan::new(1.5).unwrap());
+
+        let err = ParamsDeserializer::new(params! { "x" => "1", "y" => "NaN" })
+            .deserialize::<Point>()
+            .unwrap_err();
+        assert!(matches!(err, ParamsDeserializationError::NotANumber));
+    }
+}
+
+#[cfg(all(test, feature = "email_address"))]
+mod email_address_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_email_address() {
+        use email_address::EmailAddress;
+
+        #[derive(Debug, Deserialize)]
+        struct User {
+            email: EmailAddress,
+        }
+
+        let user: User = ParamsDeserializer::new(params! { "email" => "jane.doe@example.com" })
+            .deserialize()
+            .unwrap();
+        assert_eq!(user.email.domain(), "example.com");
+
+        let err = ParamsDeserializer::new(params! { "email" => "jane.doe" })
+            .deserialize::<User>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                if key == "email" && expected_type.ends_with("EmailAddress")
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "chrono-tz"))]
+mod chrono_tz_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_chrono_tz() {
+        use chrono_tz::Tz;
+
+        #[derive(Debug, Deserialize)]
+        struct Schedule {
+            tz: Tz,
+        }
+
+        let schedule: Schedule =
+            from_slice(&[("tz", "America%2FArgentina%2FBuenos_Aires")]).unwrap();
+        assert_eq!(schedule.tz, Tz::America__Argentina__Buenos_Aires);
+
+        let err = from_slice::<Schedule>(&[("tz", "Europe%2FAtlantis")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, ref value, expected_type, .. }
+                if key == "tz" && value == "Europe/Atlantis" && expected_type.ends_with("Tz")
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "ipnet"))]
+mod ipnet_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_ipnet() {
+        use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+        use ipnet::IpNet;
+
+        #[derive(Debug, Deserialize)]
+        struct Network {
+            ip: IpAddr,
+            v4: Ipv4Addr,
+            v6: Ipv6Addr,
+            socket: SocketAddr,
+            net: IpNet,
+        }
+
+        let network: Network = from_slice(&[
+            ("ip", "fe80%3A%3A1"),
+            ("v4", "10.0.0.1"),
+            ("v6", "%3A%3A1"),
+            ("socket", "%5B%3A%3A1%5D%3A8080"),
+            ("net", "2001%3Adb8%3A%3A%2F32"),
+        ])
+        .unwrap();
+        assert_eq!(network.ip, "fe80::1".parse::<IpAddr>().unwrap());
+        assert_eq!(network.v4, Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(network.v6, Ipv6Addr::LOCALHOST);
+        assert_eq!(network.socket.port(), 8080);
+        assert_eq!(network.net.prefix_len(), 32);
+
+        let err = from_slice::<Network>(&[
+            ("ip", "::1"),
+            ("v4", "::1"),
+            ("v6", "::1"),
+            ("socket", "[::1]:80"),
+            ("net", "::/0"),
+        ])
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, ref value, expected_type, .. }
+                if key == "v4" && value == "::1" && expected_type.ends_with("Ipv4Addr")
+        ));
+
+        let err = from_slice::<Network>(&[
+            ("ip", "::1"),
+            ("v4", "10.0.0.1"),
+            ("v6", "::1"),
+            ("socket", "[::1]:80"),
+            ("net", "10.0.0.0%2F33"),
+        ])
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                if key == "net" && expected_type.ends_with("IpNet")
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "secrecy"))]
+mod secrecy_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_secrecy() {
+        use secrecy::{ExposeSecret, SecretString};
+
+        #[derive(Debug, Deserialize)]
+        struct Callback {
+            token: SecretString,
+            #[allow(dead_code)]
+            attempt: u8,
+        }
+
+        let callback: Callback = from_slice(&[("token", "hunter%32"), ("attempt", "1")]).unwrap();
+        assert_eq!(callback.token.expose_secret(), "hunter2");
+        assert!(!format!("{callback:?}").contains("hunter2"));
+
+        let err = from_slice::<Callback>(&[("token", "hunter2"), ("attempt", "x")]).unwrap_err();
+        assert!(!err.to_string().contains("hunter2"));
+        assert!(!format!("{err:?}").contains("hunter2"));
+    }
+}
+
+#[cfg(all(test, feature = "compact_str"))]
+mod compact_str_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_compact_str() {
+        use compact_str::CompactString;
+
+        #[derive(Debug, Deserialize)]
+        struct Slugs {
+            short: CompactString,
+            long: CompactString,
+        }
+
+        let long = "a-slug-that-is-longer-than-twenty-four-bytes";
+        let slugs: Slugs = from_slice(&[("short", "my%20post"), ("long", long)]).unwrap();
+        assert_eq!(slugs.short, "my post");
+        assert!(!slugs.short.is_heap_allocated());
+        assert_eq!(slugs.long, long);
+    }
+}
+
+#[cfg(all(test, feature = "smol_str"))]
+mod smol_str_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_smol_str() {
+        use smol_str::SmolStr;
+
+        #[derive(Debug, Deserialize)]
+        struct Slugs {
+            short: SmolStr,
+            long: SmolStr,
+        }
+
+        let long = "a-slug-that-is-longer-than-twenty-three-bytes";
+        let slugs: Slugs = from_slice(&[("short", "my%20post"), ("long", long)]).unwrap();
+        assert_eq!(slugs.short, "my post");
+        assert!(!slugs.short.is_heap_allocated());
+        assert_eq!(slugs.long, long);
+    }
+}
+
+#[cfg(all(test, feature = "iri-string"))]
+mod iri_string_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_iri_string() {
+        use iri_string::types::{IriReferenceString, IriString, UriString};
+
+        #[derive(Debug, Deserialize)]
+        struct Link {
+            iri: IriString,
+            reference: IriReferenceString,
+            uri: UriString,
+        }
+
+        let link: Link = from_slice(&[
+            (
+                "iri",
+                "https%3A%2F%2Fb%C3%BCcher.example%2F%E6%97%A5%E6%9C%AC",
+            ),
+            ("reference", "..%2Fdocs%3Flang%3Dde"),
+            ("uri", "urn%3Aisbn%3A0451450523"),
+        ])
+        .unwrap();
+        assert_eq!(link.iri.as_str(), "https://bücher.example/日本");
+        assert_eq!(link.reference.as_str(), "../docs?lang=de");
+        assert_eq!(link.uri.as_str(), "urn:isbn:0451450523");
+
+        let err = from_slice::<Link>(&[
+            ("iri", "https://example.com"),
+            ("reference", "a"),
+            ("uri", "https%3A%2F%2Fb%C3%BCcher.example"),
+        ])
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                if key == "uri" && expected_type.contains("RiString")
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "validator"))]
+mod validator_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_deserialize_validated() {
+        use validator::{Validate, ValidationError, ValidationErrors};
+
+        #[derive(Debug, Deserialize)]
+        struct Page {
+            number: u32,
+        }
+
+        impl Validate for Page {
+            fn validate(&self) -> Result<(), ValidationErrors> {
+                let mut errors = ValidationErrors::new();
+                if self.number == 0 {
+                    errors.add("number", ValidationError::new("range"));
+                }
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(errors)
+                }
+            }
+        }
+
+        let page: Page = ParamsDeserializer::new(params! { "number" => "2" })
+            .deserialize_validated()
+            .unwrap();
+        assert_eq!(page.number, 2);
+
+        let err = ParamsDeserializer::new(params! { "number" => "0" })
+            .deserialize_validated::<Page>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::Validation(ref errors)
+                if errors.field_errors().contains_key("number")
+        ));
+
+        let err = ParamsDeserializer::new(params! { "number" => "x" })
+            .deserialize_validated::<Page>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { .. }
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "garde"))]
+mod garde_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_deserialize_garde() {
+        use garde::{Path, Report, Validate};
+
+        #[derive(Debug, Deserialize)]
+        struct Page {
+            number: u32,
+        }
+
+        struct Limits {
+            max_page: u32,
+        }
+
+        impl Default for Limits {
+            fn default() -> Self {
+                Self { max_page: 10 }
+            }
+        }
+
+        impl Validate for Page {
+            type Context = Limits;
+
+            fn validate_into(
+                &self,
+                ctx: &Limits,
+                parent: &mut dyn FnMut() -> Path,
+                report: &mut Report,
+            ) {
+                if self.number > ctx.max_page {
+                    report.append(parent().join("number"), garde::Error::new("too large"));
+                }
+            }
+        }
+
+        let deserializer = ParamsDeserializer::new(params! { "number" => "20" });
+        let err = deserializer.deserialize_garde::<Page>().unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::Garde(ref report)
+                if report.iter().any(|(path, _)| path.to_string() == "number")
+        ));
+        let page: Page = deserializer
+            .deserialize_garde_with(&Limits { max_page: 50 })
+            .unwrap();
+        assert_eq!(page.number, 20);
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tracing_tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_tracing() {
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Output(Arc<Mutex<Vec<u8>>>);
+
+        impl std::io::Write for Output {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        #[allow(dead_code)]
+        #[derive(Debug, Deserialize)]
+        struct Session {
+            id: u32,
+            token: String,
+        }
+
+        let output = Output::default();
+        let writer = output.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let params = params! { "id" => "x", "token" => "hunter2" };
+            ParamsDeserializer::new(params)
+                .deserialize_route::<Session>("/sessions/{id}/{token}")
+                .unwrap_err();
+            ParamsDeserializer::new(params! { "id" => "1", "tab" => "posts" })
+                .deserialize_with_unused::<Session>()
+                .unwrap_err();
+        });
+
+        let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains(r#"template="/sessions/{id}/{token}""#));
+        assert!(output.contains("params=id=[redacted] token=[redacted]"));
+        assert!(output.contains(r#"kind="parse_error_at_key" key="id""#));
+        assert!(output.contains("params=id=[redacted] tab=[redacted]"));
+        assert_eq!(output.matches("failed to deserialize params").count(), 2);
+        assert!(!output.contains("hunter2"));
     }
 }
@@ -0,0 +1,117 @@
+//! Failure diagnostics through the `log` crate, for apps not using `tracing`.
+//!
+//! When [`ParamsDeserializer::deserialize`](crate::ParamsDeserializer::deserialize) fails, a
+//! record with the error kind and key, the target type, the route template if given and the
+//! params with their values redacted is logged with the target `matchit_serde`, at the
+//! [`Debug`](::log::Level::Debug) level unless configured otherwise with [`set_level`].
+//!
+//! ```
+//! matchit_serde::log::set_level(Some(log::Level::Info));
+//! // or disable the records
+//! matchit_serde::log::set_level(None);
+//! # matchit_serde::log::set_level(Some(log::Level::Debug));
+//! ```
+
+use crate::{Params, ParamsDeserializationError, Redacted};
+use ::log::Level;
+use core::{
+    any::type_name,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static LEVEL: AtomicUsize = AtomicUsize::new(Level::Debug as usize);
+
+/// Sets the level failures are logged at, or disables logging them with `None`.
+pub fn set_level(level: Option<Level>) {
+    LEVEL.store(level.map_or(0, |level| level as usize), Ordering::Relaxed);
+}
+
+fn level() -> Option<Level> {
+    Level::iter().find(|level| *level as usize == LEVEL.load(Ordering::Relaxed))
+}
+
+pub(crate) fn failure<T>(
+    template: Option<&str>,
+    params: &Params<'_>,
+    err: &ParamsDeserializationError,
+) {
+    let Some(level) = level() else {
+        return;
+    };
+    ::log::log!(
+        target: "matchit_serde",
+        level,
+        "failed to deserialize params into {}: kind={} key={:?} template={:?} params={}",
+        type_name::<T>(),
+        err.kind(),
+        err.key(),
+        template,
+        Redacted::all(params),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParamsDeserializer, params};
+    use ::log::{Log, Metadata, Record};
+    use std::{
+        string::{String, ToString},
+        sync::Mutex,
+        vec::Vec,
+    };
+
+    struct Logger(Mutex<Vec<(Level, String)>>);
+
+    impl Log for Logger {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            // Other tests may log concurrently, and `tracing` may forward its events to `log`.
+            let message = record.args().to_string();
+            if message.starts_with("failed to deserialize params into") && message.contains("Probe")
+            {
+                self.0.lock().unwrap().push((record.level(), message));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, serde::Deserialize)]
+    struct Probe(u32, String);
+
+    static LOGGER: Logger = Logger(Mutex::new(Vec::new()));
+
+    #[test]
+    fn test_failure() {
+        ::log::set_logger(&LOGGER).unwrap();
+        ::log::set_max_level(::log::LevelFilter::Trace);
+
+        let params = params! { "id" => "x", "token" => "hunter2" };
+        let deserializer = ParamsDeserializer::new(params);
+        deserializer
+            .deserialize_route::<Probe>("/{id}/{token}")
+            .unwrap_err();
+        set_level(Some(Level::Warn));
+        deserializer.deserialize::<Probe>().unwrap_err();
+        set_level(None);
+        deserializer.deserialize::<Probe>().unwrap_err();
+        set_level(Some(Level::Debug));
+
+        let records = LOGGER.0.lock().unwrap();
+        assert_eq!(
+            records.iter().map(|(level, _)| *level).collect::<Vec<_>>(),
+            [Level::Debug, Level::Warn]
+        );
+        assert_eq!(
+            records[0].1,
+            "failed to deserialize params into matchit_serde::log::tests::Probe: \
+             kind=parse_error_at_index key=None template=Some(\"/{id}/{token}\") \
+             params=id=[redacted] token=[redacted]"
+        );
+    }
+}
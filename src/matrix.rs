@@ -0,0 +1,116 @@
+use crate::{Conflict, Decoding, Params, ParamsDeserializationError};
+use alloc::{string::String, vec::Vec};
+use core::str::Utf8Error;
+
+/// A path with its `;key=value` matrix params split off.
+///
+/// matchit doesn't understand matrix params, so the path has to be stripped of them before
+/// matching. The stripped path is matched as usual and the matrix params are added to the
+/// matched params with [`Params::with_matrix`].
+///
+/// ```
+/// use matchit_serde::{MatrixPath, Params};
+///
+/// let mut router = matchit::Router::new();
+/// router.insert("/users/{id}", ()).unwrap();
+///
+/// let matrix = MatrixPath::parse("/users;role=admin;active=true/42");
+/// assert_eq!(matrix.path(), "/users/42");
+///
+/// let matched = router.at(matrix.path()).unwrap();
+/// let params = Params::try_from(&matched.params)
+///     .unwrap()
+///     .with_matrix(&matrix)
+///     .unwrap();
+/// assert_eq!(params.get("id"), Some("42"));
+/// assert_eq!(params.get("role"), Some("admin"));
+/// assert_eq!(params.get("active"), Some("true"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixPath<'a> {
+    path: String,
+    params: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> MatrixPath<'a> {
+    /// Splits the matrix params off every segment of `path`.
+    ///
+    /// A matrix param without `=` gets an empty value.
+    pub fn parse(path: &'a str) -> Self {
+        let mut stripped = String::with_capacity(path.len());
+        let mut params = Vec::new();
+        for (idx, segment) in path.split('/').enumerate() {
+            if idx > 0 {
+                stripped.push('/');
+            }
+            let mut parts = segment.split(';');
+            stripped.push_str(parts.next().unwrap_or_default());
+            params.extend(
+                parts
+                    .filter(|part| !part.is_empty())
+                    .map(|part| part.split_once('=').unwrap_or((part, ""))),
+            );
+        }
+        Self {
+            path: stripped,
+            params,
+        }
+    }
+
+    /// The path without matrix params, to be matched by the router.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The raw, not yet decoded matrix params in the order they occur in the path.
+    pub fn params(&self) -> &[(&'a str, &'a str)] {
+        &self.params
+    }
+}
+
+impl<'de> Params<'de> {
+    /// Adds the percent-decoded matrix params of `matrix`. Keys which are already present take
+    /// precedence over matrix params.
+    pub fn with_matrix(
+        mut self,
+        matrix: &MatrixPath<'de>,
+    ) -> Result<Self, ParamsDeserializationError> {
+        let matrix = matrix
+            .params
+            .iter()
+            .map(|(k, v)| Ok((Decoding::Percent.decode(k)?, Decoding::Percent.decode(v)?)))
+            .collect::<Result<Vec<_>, Utf8Error>>()?;
+        self.merge(Params(matrix), Conflict::Keep)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params;
+
+    #[test]
+    fn test_parse() {
+        let matrix = MatrixPath::parse("/users;role=admin;;flag/42;v=a%20b/");
+        assert_eq!(matrix.path(), "/users/42/");
+        assert_eq!(
+            matrix.params(),
+            &[("role", "admin"), ("flag", ""), ("v", "a%20b")]
+        );
+
+        let plain = MatrixPath::parse("/users/42");
+        assert_eq!(plain.path(), "/users/42");
+        assert!(plain.params().is_empty());
+    }
+
+    #[test]
+    fn test_with_matrix() {
+        let matrix = MatrixPath::parse("/users;id=1;v=a%20b/2");
+        let params = params! { "id" => "2" }.with_matrix(&matrix).unwrap();
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("id", "2"), ("v", "a b")]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Failure counters through the `metrics` crate, to alert on spikes of malformed params.
+//!
+//! When [`ParamsDeserializer::deserialize`](crate::ParamsDeserializer::deserialize) fails, the
+//! [`FAILURES`] counter is incremented with the error kind as the `kind` label, and the route
+//! template given to [`deserialize_route`](crate::ParamsDeserializer::deserialize_route) as the
+//! `template` label.
+
+use crate::ParamsDeserializationError;
+use ::metrics::Label;
+use std::{borrow::ToOwned, vec::Vec};
+
+/// The name of the failure counter.
+pub const FAILURES: &str = "matchit_serde_failures_total";
+
+pub(crate) fn failure(template: Option<&str>, err: &ParamsDeserializationError) {
+    let mut labels = Vec::with_capacity(2);
+    labels.push(Label::new("kind", err.kind()));
+    if let Some(template) = template {
+        labels.push(Label::new("template", template.to_owned()));
+    }
+    ::metrics::counter!(FAILURES, labels).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParamsDeserializer, params};
+    use metrics_util::{
+        CompositeKey,
+        debugging::{DebugValue, DebuggingRecorder},
+    };
+
+    #[test]
+    fn test_failure_counter() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        ::metrics::with_local_recorder(&recorder, || {
+            let deserializer = ParamsDeserializer::new(params! { "id" => "x" });
+            for _ in 0..2 {
+                deserializer
+                    .deserialize_route::<u32>("/users/{id}")
+                    .unwrap_err();
+            }
+            deserializer.deserialize::<(u32, u32)>().unwrap_err();
+        });
+
+        let mut counters = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value): (CompositeKey, _, _, _)| {
+                let key = key.key();
+                assert_eq!(key.name(), FAILURES);
+                let labels = key
+                    .labels()
+                    .map(|label| (label.key().to_owned(), label.value().to_owned()))
+                    .collect::<Vec<_>>();
+                (labels, value)
+            })
+            .collect::<Vec<_>>();
+        counters.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            counters,
+            [
+                (
+                    vec![
+                        ("kind".to_owned(), "parse_error".to_owned()),
+                        ("template".to_owned(), "/users/{id}".to_owned())
+                    ],
+                    DebugValue::Counter(2)
+                ),
+                (
+                    vec![("kind".to_owned(), "wrong_number_of_parameters".to_owned())],
+                    DebugValue::Counter(1)
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! `#[serde(with)]` helpers for [`Mime`] params, whose slash is percent-encoded as `%2F` in a
+//! single segment.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use mime::Mime;
+//!
+//! #[derive(Deserialize)]
+//! struct Convert {
+//!     #[serde(with = "matchit_serde::mime")]
+//!     from: Mime,
+//!     #[serde(with = "matchit_serde::mime")]
+//!     to: Mime,
+//! }
+//!
+//! let convert: Convert =
+//!     matchit_serde::from_slice(&[("from", "text%2Fmarkdown"), ("to", "application%2Fpdf")])
+//!         .unwrap();
+//! assert_eq!(convert.to, mime::APPLICATION_PDF);
+//! ```
+
+use crate::parse::ParseVisitor;
+use ::mime::Mime;
+use core::marker::PhantomData;
+use serde::{Deserializer, Serializer};
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Mime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |value| value.parse().ok(),
+        expecting: "a media type like `text/plain`",
+        marker: PhantomData,
+    })
+}
+
+pub fn serialize<S>(value: &Mime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(value.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use mime::Mime;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Convert {
+        #[serde(with = "super")]
+        from: Mime,
+        #[serde(with = "super")]
+        to: Mime,
+    }
+
+    #[test]
+    fn test_mime() {
+        let convert: Convert = from_slice(&[
+            ("from", "text%2Fplain%3B%20charset%3Dutf-8"),
+            ("to", "image%2Fsvg%2Bxml"),
+        ])
+        .unwrap();
+        assert_eq!(convert.from, mime::TEXT_PLAIN_UTF_8);
+        assert_eq!(convert.to, mime::IMAGE_SVG);
+        assert_eq!(
+            to_pairs(&convert).unwrap(),
+            [
+                ("from", "text/plain; charset=utf-8"),
+                ("to", "image/svg+xml")
+            ]
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+        );
+
+        let err = from_slice::<Convert>(&[("from", "text"), ("to", "image%2Fpng")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                if key == "from" && expected_type.ends_with("Mime")
+        ));
+    }
+}
@@ -0,0 +1,101 @@
+//! Extractor for ntex apps which match routes with their own [`matchit::Router`] in a
+//! middleware.
+
+use crate::{ParamsBuf, ParamsDeserializationError};
+use ::ntex::{
+    http::{Payload, StatusCode},
+    web::{DefaultError, ErrorRenderer, FromRequest, HttpRequest, WebResponseError},
+};
+use serde::de::DeserializeOwned;
+
+/// Extracts `T` from the [`ParamsBuf`] the middleware stored in the request extensions.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::{ParamsBuf, ntex::MatchitPath};
+///
+/// #[derive(Deserialize)]
+/// struct UserPost {
+///     user: String,
+///     post: u32,
+/// }
+///
+/// async fn handler(MatchitPath(params): MatchitPath<UserPost>) -> String {
+///     format!("post {} by {}", params.post, params.user)
+/// }
+///
+/// // in the middleware
+/// # let request = ntex::web::test::TestRequest::default().to_http_request();
+/// let mut router = matchit::Router::new();
+/// router.insert("/users/{user}/posts/{post}", ()).unwrap();
+/// let matched = router.at("/users/alice/posts/42").unwrap();
+/// request
+///     .extensions_mut()
+///     .insert(ParamsBuf::try_from(&matched.params).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchitPath<T>(pub T);
+
+impl<T, Err> FromRequest<Err> for MatchitPath<T>
+where
+    T: DeserializeOwned,
+    Err: ErrorRenderer,
+{
+    type Error = ParamsDeserializationError;
+
+    async fn from_request(req: &HttpRequest, _: &mut Payload) -> Result<Self, Self::Error> {
+        req.extensions()
+            .get::<ParamsBuf>()
+            .ok_or(ParamsDeserializationError::MissingParams)
+            .and_then(ParamsBuf::deserialize)
+            .map(Self)
+    }
+}
+
+/// Responds with `500 Internal Server Error` to
+/// [server errors](ParamsDeserializationError::is_server_error) and with `400 Bad Request`
+/// otherwise.
+impl WebResponseError<DefaultError> for ParamsDeserializationError {
+    fn status_code(&self) -> StatusCode {
+        if self.is_server_error() {
+            StatusCode::INTERNAL_SERVER_ERROR
+        } else {
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MatchitPath;
+    use crate::{ParamsBuf, ParamsDeserializationError, test_util::poll_once};
+    use ntex::{
+        http::{Payload, StatusCode},
+        web::{DefaultError, FromRequest, WebResponseError, test::TestRequest},
+    };
+
+    #[test]
+    fn test_error_response() {
+        let req = TestRequest::default().to_http_request();
+        let err = poll_once(
+            <MatchitPath<u32> as FromRequest<DefaultError>>::from_request(&req, &mut Payload::None),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ParamsDeserializationError::MissingParams));
+        assert_eq!(
+            WebResponseError::<DefaultError>::error_response(&err, &req).status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        req.extensions_mut()
+            .insert(ParamsBuf::from(vec![("id".to_owned(), "x".to_owned())]));
+        let err = poll_once(
+            <MatchitPath<u32> as FromRequest<DefaultError>>::from_request(&req, &mut Payload::None),
+        )
+        .unwrap_err();
+        assert_eq!(
+            WebResponseError::<DefaultError>::error_response(&err, &req).status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
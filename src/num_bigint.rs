@@ -0,0 +1,78 @@
+//! `#[serde(with)]` helpers for decimal [`BigInt`](::num_bigint::BigInt) and
+//! [`BigUint`](::num_bigint::BigUint) params.
+//!
+//! The serde impls of `num-bigint` use a sequence of digits, so these helpers parse the decimal
+//! string instead. `BigInt` accepts a leading `-` or `+`, `BigUint` rejects negative values.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use num_bigint::BigUint;
+//!
+//! #[derive(Deserialize)]
+//! struct Block {
+//!     #[serde(with = "matchit_serde::num_bigint")]
+//!     number: BigUint,
+//! }
+//!
+//! let block: Block =
+//!     matchit_serde::from_slice(&[("number", "340282366920938463463374607431768211456")]).unwrap();
+//! assert_eq!(block.number, BigUint::from(u128::MAX) + 1u8);
+//! ```
+
+use crate::parse::ParseVisitor;
+use core::{fmt::Display, marker::PhantomData, str::FromStr};
+use serde::{Deserializer, Serializer};
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |value| value.parse().ok(),
+        expecting: "a decimal integer",
+        marker: PhantomData,
+    })
+}
+
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use num_bigint::{BigInt, BigUint};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Values {
+        #[serde(with = "super")]
+        signed: BigInt,
+        #[serde(with = "super")]
+        unsigned: BigUint,
+    }
+
+    #[test]
+    fn test_big_integers() {
+        let big = "-123456789012345678901234567890123456789";
+        let values: Values = from_slice(&[("signed", big), ("unsigned", "%2B42")]).unwrap();
+        assert_eq!(values.signed.to_string(), big);
+        assert_eq!(values.unsigned, BigUint::from(42u8));
+        assert_eq!(
+            to_pairs(&values).unwrap(),
+            [("signed", big), ("unsigned", "42")].map(|(k, v)| (k.to_owned(), v.to_owned()))
+        );
+
+        let err = from_slice::<Values>(&[("signed", "1"), ("unsigned", "-1")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                if key == "unsigned" && expected_type.ends_with("BigUint")
+        ));
+    }
+}
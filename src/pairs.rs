@@ -0,0 +1,358 @@
+use crate::ParamsDeserializationError;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::ser::{self, Impossible, Serialize};
+
+/// Serializes a struct or map of scalar values into key/value pairs.
+///
+/// `None` and unit values are skipped, so deserializing the pairs again treats them as missing.
+pub(crate) fn to_pairs<T>(value: &T) -> Result<Vec<(String, String)>, ParamsDeserializationError>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(PairsSerializer)
+}
+
+impl ser::Error for ParamsDeserializationError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: core::fmt::Display,
+    {
+        Self::Custom(msg.to_string())
+    }
+}
+
+macro_rules! unsupported {
+    ($($trait_fn:ident($($arg:ty),*) -> $ok:ty;)*) => {
+        $(fn $trait_fn(self, $(_: $arg),*) -> Result<$ok, Self::Error> {
+            Err(ParamsDeserializationError::UnsupportedType(stringify!($trait_fn)))
+        })*
+    };
+}
+
+struct PairsSerializer;
+
+impl ser::Serializer for PairsSerializer {
+    type Ok = Vec<(String, String)>;
+    type Error = ParamsDeserializationError;
+    type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = PairsCollector;
+    type SerializeStruct = PairsCollector;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    unsupported! {
+        serialize_bool(bool) -> Self::Ok;
+        serialize_i8(i8) -> Self::Ok;
+        serialize_i16(i16) -> Self::Ok;
+        serialize_i32(i32) -> Self::Ok;
+        serialize_i64(i64) -> Self::Ok;
+        serialize_u8(u8) -> Self::Ok;
+        serialize_u16(u16) -> Self::Ok;
+        serialize_u32(u32) -> Self::Ok;
+        serialize_u64(u64) -> Self::Ok;
+        serialize_f32(f32) -> Self::Ok;
+        serialize_f64(f64) -> Self::Ok;
+        serialize_char(char) -> Self::Ok;
+        serialize_str(&str) -> Self::Ok;
+        serialize_bytes(&[u8]) -> Self::Ok;
+        serialize_unit_variant(&'static str, u32, &'static str) -> Self::Ok;
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(Vec::new())
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(ParamsDeserializationError::UnsupportedType(
+            "serialize_newtype_variant",
+        ))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(PairsCollector {
+            pairs: Vec::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+}
+
+struct PairsCollector {
+    pairs: Vec<(String, String)>,
+    key: Option<String>,
+}
+
+impl PairsCollector {
+    fn push<T>(&mut self, key: String, value: &T) -> Result<(), ParamsDeserializationError>
+    where
+        T: Serialize + ?Sized,
+    {
+        if let Some(value) = value.serialize(ValueSerializer)? {
+            self.pairs.push((key, value));
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for PairsCollector {
+    type Ok = Vec<(String, String)>;
+    type Error = ParamsDeserializationError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(ValueSerializer)?.ok_or(
+            ParamsDeserializationError::UnsupportedType("serialize_none"),
+        )?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self.key.take().expect("serialize_key is called first");
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.pairs)
+    }
+}
+
+impl ser::SerializeStruct for PairsCollector {
+    type Ok = Vec<(String, String)>;
+    type Error = ParamsDeserializationError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.pairs)
+    }
+}
+
+macro_rules! serialize_display {
+    ($($trait_fn:ident($ty:ty);)*) => {
+        $(fn $trait_fn(self, value: $ty) -> Result<Self::Ok, Self::Error> {
+            Ok(Some(value.to_string()))
+        })*
+    };
+}
+
+/// Serializes a single scalar value, `None` for missing values.
+struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Option<String>;
+    type Error = ParamsDeserializationError;
+    type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    serialize_display! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_i128(i128);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_u128(u128);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_str(&str);
+    }
+
+    unsupported! {
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(String::from(core::str::from_utf8(value)?)))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(ParamsDeserializationError::UnsupportedType(
+            "serialize_newtype_variant",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_pairs;
+    use crate::ParamsDeserializationError;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    enum Sort {
+        Asc,
+    }
+
+    #[derive(Serialize)]
+    struct Defaults {
+        page: u32,
+        ratio: f64,
+        sort: Sort,
+        tag: Option<&'static str>,
+        active: bool,
+    }
+
+    #[test]
+    fn test_to_pairs() {
+        let pairs = to_pairs(&Defaults {
+            page: 1,
+            ratio: 0.5,
+            sort: Sort::Asc,
+            tag: None,
+            active: true,
+        })
+        .unwrap();
+        assert_eq!(
+            pairs,
+            [
+                ("page", "1"),
+                ("ratio", "0.5"),
+                ("sort", "Asc"),
+                ("active", "true")
+            ]
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_nested_unsupported() {
+        assert!(matches!(
+            to_pairs(
+                &[("a", vec![1])]
+                    .into_iter()
+                    .collect::<std::collections::BTreeMap<_, _>>()
+            ),
+            Err(ParamsDeserializationError::UnsupportedType("serialize_seq"))
+        ));
+        assert!(matches!(
+            to_pairs(&1),
+            Err(ParamsDeserializationError::UnsupportedType("serialize_i32"))
+        ));
+    }
+}
@@ -0,0 +1,24 @@
+use core::{fmt, marker::PhantomData};
+use serde::de;
+
+/// Parses the string value with `parse`, failing with the crate's key-aware parse error.
+pub(crate) struct ParseVisitor<T> {
+    pub(crate) parse: fn(&str) -> Option<T>,
+    pub(crate) expecting: &'static str,
+    pub(crate) marker: PhantomData<T>,
+}
+
+impl<T> de::Visitor<'_> for ParseVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        (self.parse)(value).ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
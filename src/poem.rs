@@ -0,0 +1,83 @@
+//! Extractor for poem apps which match routes with their own [`matchit::Router`], for example
+//! for plugin routes below a wildcard.
+
+use crate::{ExtensionParams, ParamsDeserializationError};
+use ::poem::{FromRequest, Request, RequestBody, error::ResponseError, http::StatusCode};
+use serde::de::DeserializeOwned;
+
+/// Extracts `T` from the [`ParamsBuf`](crate::ParamsBuf) the sub-router stored in the request
+/// extensions.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::{ParamsBuf, poem::MatchitPath};
+///
+/// #[derive(Deserialize)]
+/// struct PluginRoute {
+///     plugin: String,
+///     id: u32,
+/// }
+///
+/// #[poem::handler]
+/// fn handler(MatchitPath(route): MatchitPath<PluginRoute>) -> String {
+///     format!("{} {}", route.plugin, route.id)
+/// }
+///
+/// // in the middleware doing the sub-routing
+/// let mut router = matchit::Router::new();
+/// router.insert("/{plugin}/items/{id}", ()).unwrap();
+/// let matched = router.at("/search/items/42").unwrap();
+/// let mut request = poem::Request::builder().finish();
+/// request
+///     .extensions_mut()
+///     .insert(ParamsBuf::try_from(&matched.params).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchitPath<T>(pub T);
+
+impl<'a, T> FromRequest<'a> for MatchitPath<T>
+where
+    T: DeserializeOwned,
+{
+    async fn from_request(req: &'a Request, _: &mut RequestBody) -> ::poem::Result<Self> {
+        let ExtensionParams(params) = ExtensionParams::from_extensions(req.extensions())?;
+        Ok(Self(params))
+    }
+}
+
+/// Responds with `500 Internal Server Error` to
+/// [server errors](ParamsDeserializationError::is_server_error) and with `400 Bad Request`
+/// otherwise.
+impl ResponseError for ParamsDeserializationError {
+    fn status(&self) -> StatusCode {
+        if self.is_server_error() {
+            StatusCode::INTERNAL_SERVER_ERROR
+        } else {
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MatchitPath;
+    use crate::{ParamsBuf, ParamsDeserializationError, test_util::poll_once};
+    use poem::{FromRequest, Request, http::StatusCode};
+
+    #[test]
+    fn test_error() {
+        let mut req = Request::builder().finish();
+        req.extensions_mut()
+            .insert(ParamsBuf::from(vec![("id".to_owned(), "x".to_owned())]));
+        let err = poll_once(MatchitPath::<u32>::from_request_without_body(&req)).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref(),
+            Some(ParamsDeserializationError::ParseError { .. })
+        ));
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+
+        let req = Request::builder().finish();
+        let err = poll_once(MatchitPath::<u32>::from_request_without_body(&req)).unwrap_err();
+        assert_eq!(err.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,82 @@
+//! `#[serde(with)]` helpers turning a `bool` field into a flag which is `true` if its key is
+//! present at all, whatever the value, and `false` if it is missing.
+//!
+//! The field needs `#[serde(default)]` as well, so a missing key is `false` instead of an error.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Report {
+//!     id: u32,
+//!     #[serde(default, with = "matchit_serde::presence")]
+//!     detailed: bool,
+//! }
+//!
+//! let report: Report = matchit_serde::from_slice(&[("id", "1"), ("detailed", "")]).unwrap();
+//! assert!(report.detailed);
+//! let report: Report = matchit_serde::from_slice(&[("id", "1")]).unwrap();
+//! assert!(!report.detailed);
+//! ```
+
+use crate::parse::ParseVisitor;
+use core::marker::PhantomData;
+use serde::{Deserializer, Serializer};
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |_| Some(true),
+        expecting: "any value",
+        marker: PhantomData,
+    })
+}
+
+/// Serializes `true` as an empty value and `false` as a missing key.
+pub fn serialize<S>(value: &bool, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if *value {
+        serializer.serialize_str("")
+    } else {
+        serializer.serialize_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_slice, pairs::to_pairs};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Report {
+        #[serde(default, with = "super")]
+        detailed: bool,
+        #[serde(default, with = "super")]
+        raw: bool,
+    }
+
+    #[test]
+    fn test_presence() {
+        for value in ["", "true", "false", "0", "%20"] {
+            let report: Report = from_slice(&[("detailed", value)]).unwrap_or_else(|err| {
+                panic!("{value:?}: {err}");
+            });
+            assert!(report.detailed);
+            assert!(!report.raw);
+        }
+
+        let report: Report = from_slice(&[]).unwrap();
+        assert!(!report.detailed && !report.raw);
+        assert_eq!(
+            to_pairs(&Report {
+                detailed: true,
+                raw: false
+            })
+            .unwrap(),
+            [("detailed".to_owned(), String::new())]
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{Conflict, Params};
+use alloc::vec::Vec;
+
+impl<'de> Params<'de> {
+    /// Parses a form-urlencoded query string into params. A leading `?` is ignored.
+    pub fn from_query(query: &'de str) -> Self {
+        let query = query.strip_prefix('?').unwrap_or(query);
+        Self(form_urlencoded::parse(query.as_bytes()).collect::<Vec<_>>())
+    }
+
+    /// Adds the params of a form-urlencoded query string. Keys which are already present (for
+    /// example as path params) take precedence over the query.
+    pub fn with_query(mut self, query: &'de str) -> Self {
+        self.merge(Self::from_query(query), Conflict::Keep)
+            .expect("merging with Conflict::Keep doesn't fail");
+        self
+    }
+}
+
+/// Deserializes `T` from the params of a matched route combined with a query string.
+///
+/// Path params take precedence over query params with the same key.
+///
+/// ```
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct ListPosts {
+///     user: String,
+///     page: u32,
+/// }
+///
+/// let mut router = matchit::Router::new();
+/// router.insert("/users/{user}/posts", ()).unwrap();
+/// let matched = router.at("/users/alice/posts").unwrap();
+///
+/// let list: ListPosts =
+///     matchit_serde::from_path_and_query(&matched.params, "user=bob&page=2").unwrap();
+/// assert_eq!(list.user, "alice");
+/// assert_eq!(list.page, 2);
+/// ```
+#[cfg(feature = "std")]
+pub fn from_path_and_query<'de, T>(
+    params: &'de matchit::Params<'de, 'de>,
+    query: &'de str,
+) -> Result<T, crate::ParamsDeserializationError>
+where
+    T: serde::Deserialize<'de>,
+{
+    crate::ParamsDeserializer::new(Params::try_from(params)?.with_query(query)).deserialize()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Params, params};
+
+    #[test]
+    fn test_from_query() {
+        let params = Params::from_query("?name=a+b&tag=%F0%9F%A6%80&empty=");
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("name", "a b"), ("tag", "🦀"), ("empty", "")]
+        );
+    }
+
+    #[test]
+    fn test_path_precedence() {
+        let params = params! { "id" => "1" }.with_query("id=2&q%5B%5D=x");
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("id", "1"), ("q[]", "x")]
+        );
+    }
+}
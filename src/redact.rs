@@ -0,0 +1,84 @@
+use crate::Params;
+use core::fmt;
+
+const MASK: &str = "[redacted]";
+
+/// Wrapper around [`Params`] whose `Debug` and `Display` impls mask the values of sensitive keys.
+///
+/// Created by [`Params::redacted`].
+///
+/// ```
+/// let params = matchit_serde::params! { "user" => "alice", "token" => "hunter2" };
+/// let redacted = params.redacted(&["token"]);
+/// assert_eq!(redacted.to_string(), "user=alice token=[redacted]");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Redacted<'a, 'de> {
+    params: &'a Params<'de>,
+    sensitive: &'a [&'a str],
+    all: bool,
+}
+
+impl<'a, 'de> Redacted<'a, 'de> {
+    pub(crate) fn new(params: &'a Params<'de>, sensitive: &'a [&'a str]) -> Self {
+        Self {
+            params,
+            sensitive,
+            all: false,
+        }
+    }
+
+    /// Masks the values of all keys, for diagnostics which must not contain any values.
+    #[cfg(any(feature = "tracing", feature = "log"))]
+    pub(crate) fn all(params: &'a Params<'de>) -> Self {
+        Self {
+            params,
+            sensitive: &[],
+            all: true,
+        }
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.params.iter().map(|(key, value)| {
+            if self.all || self.sensitive.contains(&key) {
+                (key, MASK)
+            } else {
+                (key, value)
+            }
+        })
+    }
+}
+
+impl fmt::Debug for Redacted<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.entries()).finish()
+    }
+}
+
+impl fmt::Display for Redacted<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (key, value)) in self.entries().enumerate() {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::params;
+
+    #[test]
+    fn test_redacted() {
+        let params = params! { "user" => "alice", "token" => "hunter2" };
+        let redacted = params.redacted(&["token", "email"]);
+        assert_eq!(
+            format!("{redacted:?}"),
+            r#"{"user": "alice", "token": "[redacted]"}"#
+        );
+        assert_eq!(redacted.to_string(), "user=alice token=[redacted]");
+    }
+}
@@ -0,0 +1,107 @@
+//! Keys of entries adapters add to the params besides the ones of the route. Struct fields
+//! receive them by being renamed to the key.
+
+use crate::{Params, ParamsDeserializationError};
+use alloc::{borrow::Cow, vec::Vec};
+use core::cell::Cell;
+use serde::de::{self, Deserialize, Visitor};
+
+/// The full path of the request as it was matched, still percent-encoded.
+///
+/// Adapters add it with [`Params::with_raw_path`](crate::Params::with_raw_path), and it is only
+/// passed on to structs with a field renamed to it, so other targets are unaffected.
+///
+/// ```
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct User {
+///     id: u32,
+///     #[serde(rename = "$raw_path")]
+///     path: String,
+/// }
+/// ```
+pub const RAW_PATH: &str = "$raw_path";
+
+/// The template of the matched route, like `/users/{id}`.
+///
+/// Added by [`ParamsDeserializer::deserialize_route`](crate::ParamsDeserializer::deserialize_route)
+/// for structs with a field renamed to it, so metrics and audit logs can be labeled by route.
+///
+/// ```
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct User {
+///     id: u32,
+///     #[serde(rename = "$template")]
+///     route: String,
+/// }
+/// ```
+pub const TEMPLATE: &str = "$template";
+
+/// Returns the fields of `T` if it is a struct.
+pub(crate) fn fields<'de, T: Deserialize<'de>>() -> Option<&'static [&'static str]> {
+    let fields = Cell::new(None);
+    let _ = T::deserialize(Probe(&fields));
+    fields.get()
+}
+
+/// Returns the params `T` is deserialized from, if they differ from `params`: `template` is added
+/// under [`TEMPLATE`] and reserved entries are dropped, unless `T` has a field for them.
+pub(crate) fn prepare<'de, T: Deserialize<'de>>(
+    params: &Params<'de>,
+    template: Option<&str>,
+) -> Option<Params<'de>> {
+    let raw_path = params.get(RAW_PATH).is_some();
+    if template.is_none() && !raw_path {
+        return None;
+    }
+    let fields = fields::<T>().unwrap_or_default();
+    let template = template.filter(|_| fields.contains(&TEMPLATE));
+    let drop_raw_path = raw_path && !fields.contains(&RAW_PATH);
+    if template.is_none() && !drop_raw_path {
+        return None;
+    }
+    let mut params: Vec<_> = params
+        .0
+        .iter()
+        .filter(|(key, _)| !drop_raw_path || key != RAW_PATH)
+        .cloned()
+        .collect();
+    if let Some(template) = template {
+        params.push((Cow::Borrowed(TEMPLATE), Cow::Owned(template.into())));
+    }
+    Some(Params(params))
+}
+
+/// Records the fields of the struct it is asked for and fails every request.
+struct Probe<'a>(&'a Cell<Option<&'static [&'static str]>>);
+
+impl<'de> de::Deserializer<'de> for Probe<'_> {
+    type Error = ParamsDeserializationError;
+
+    fn deserialize_any<V>(self, _: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(ParamsDeserializationError::MissingParams)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.set(Some(fields));
+        Err(ParamsDeserializationError::MissingParams)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
@@ -0,0 +1,78 @@
+//! Adapter for the path params matched by salvo's router.
+
+use crate::{Params, ParamsDeserializationError, ParamsDeserializer};
+use alloc::{borrow::Cow, string::ToString};
+use salvo_core::{
+    http::{Request, Response, StatusError},
+    routing::PathParams,
+    writing::Scribe,
+};
+
+/// Borrows the path params matched by salvo. They are already percent-decoded by salvo.
+impl<'de> From<&'de PathParams> for Params<'de> {
+    fn from(params: &'de PathParams) -> Self {
+        Self(
+            params
+                .iter()
+                .map(|(k, v)| (Cow::Borrowed(k.as_str()), Cow::Borrowed(v.as_str())))
+                .collect(),
+        )
+    }
+}
+
+/// Deserializes `T` from the path params salvo matched for `req`.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use salvo_core::{Request, Response, handler, writing::Text};
+///
+/// #[derive(Deserialize)]
+/// struct UserPost {
+///     user: String,
+///     post: u32,
+/// }
+///
+/// #[handler]
+/// async fn show(req: &mut Request, res: &mut Response) {
+///     match matchit_serde::salvo::from_request::<UserPost>(req) {
+///         Ok(params) => res.render(Text::Plain(format!("post {} by {}", params.post, params.user))),
+///         Err(err) => res.render(err),
+///     }
+/// }
+/// ```
+pub fn from_request<'de, T>(req: &'de Request) -> Result<T, ParamsDeserializationError>
+where
+    T: serde::Deserialize<'de>,
+{
+    ParamsDeserializer::new(Params::from(req.params())).deserialize()
+}
+
+/// Renders `400 Bad Request` with the error message.
+impl Scribe for ParamsDeserializationError {
+    fn render(self, res: &mut Response) {
+        res.render(StatusError::bad_request().brief(self.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_request;
+    use salvo_core::{
+        http::{Request, Response, StatusCode},
+        writing::Scribe,
+    };
+
+    #[test]
+    fn test_from_request() {
+        let mut req = Request::new();
+        req.params_mut().insert("id", "7".to_owned());
+        req.params_mut().insert("name", "a b".to_owned());
+        assert_eq!(from_request::<(u32, &str)>(&req).unwrap(), (7, "a b"));
+
+        let mut res = Response::new();
+        from_request::<(bool, bool)>(&req)
+            .unwrap_err()
+            .render(&mut res);
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+    }
+}
@@ -0,0 +1,206 @@
+//! `#[serde(with)]` helpers clamping out-of-range integer params to the bounds of their type
+//! instead of failing, for endpoints preferring best-effort parsing.
+//!
+//! Only values consisting of digits with an optional sign are clamped, anything else still fails
+//! with a parse error at its key. Fields using [`deserialize_with_hook`] are told about clamped
+//! values.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Listing {
+//!     #[serde(with = "matchit_serde::saturating")]
+//!     per_page: u8,
+//!     #[serde(with = "matchit_serde::saturating")]
+//!     offset: i16,
+//! }
+//!
+//! let listing: Listing =
+//!     matchit_serde::from_slice(&[("per_page", "1000"), ("offset", "-99999")]).unwrap();
+//! assert_eq!(listing.per_page, 255);
+//! assert_eq!(listing.offset, i16::MIN);
+//! ```
+
+use core::{any::type_name, fmt, marker::PhantomData, str::FromStr};
+use serde::{Deserializer, Serializer, de};
+
+/// A primitive integer type values can be clamped to.
+pub trait SaturatingInt: FromStr + fmt::Display + Sized {
+    const MIN: Self;
+    const MAX: Self;
+}
+
+macro_rules! saturating_int {
+    ($($ty:ty)*) => {
+        $(
+            impl SaturatingInt for $ty {
+                const MIN: Self = <$ty>::MIN;
+                const MAX: Self = <$ty>::MAX;
+            }
+        )*
+    };
+}
+
+saturating_int!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+/// Parses `value`, clamping it and calling `on_clamp` if it is out of range.
+fn parse<T: SaturatingInt>(value: &str, on_clamp: impl FnOnce(&str, &'static str)) -> Option<T> {
+    if let Ok(value) = value.parse() {
+        return Some(value);
+    }
+    let (negative, digits) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    on_clamp(value, type_name::<T>());
+    Some(if negative { T::MIN } else { T::MAX })
+}
+
+struct SaturatingVisitor<T, F> {
+    on_clamp: F,
+    marker: PhantomData<T>,
+}
+
+impl<T, F> de::Visitor<'_> for SaturatingVisitor<T, F>
+where
+    T: SaturatingInt,
+    F: FnOnce(&str, &'static str),
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(type_name::<T>())
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        let expecting = type_name::<T>();
+        parse(value, self.on_clamp)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &expecting))
+    }
+}
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: SaturatingInt,
+{
+    deserialize_with_hook(deserializer, |_, _| {})
+}
+
+/// Like [`deserialize`], calling `on_clamp` with the value and the name of the target type if
+/// the value is clamped.
+///
+/// ```
+/// # use serde::{Deserialize, Deserializer};
+/// fn per_page<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+///     matchit_serde::saturating::deserialize_with_hook(deserializer, |value, ty| {
+///         eprintln!("clamped {value} to {ty}");
+///     })
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Listing {
+///     #[serde(deserialize_with = "per_page")]
+///     per_page: u8,
+/// }
+/// ```
+pub fn deserialize_with_hook<'de, D, T>(
+    deserializer: D,
+    on_clamp: impl FnOnce(&str, &'static str),
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: SaturatingInt,
+{
+    deserializer.deserialize_str(SaturatingVisitor {
+        on_clamp,
+        marker: PhantomData,
+    })
+}
+
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: SaturatingInt,
+{
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Listing {
+        #[serde(with = "super")]
+        page: u32,
+        #[serde(with = "super")]
+        offset: i8,
+    }
+
+    #[test]
+    fn test_clamping() {
+        for (page, offset, expected) in [
+            ("7", "-3", (7, -3)),
+            ("99999999999", "200", (u32::MAX, i8::MAX)),
+            ("-1", "-129", (0, i8::MIN)),
+            ("%2B5000000000", "%2B1", (u32::MAX, 1)),
+        ] {
+            let listing: Listing = from_slice(&[("page", page), ("offset", offset)]).unwrap();
+            assert_eq!((listing.page, listing.offset), expected);
+        }
+
+        for page in ["1.5", "-", "", "12a", "1e10"] {
+            let err = from_slice::<Listing>(&[("page", page), ("offset", "0")]).unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type: "u32", .. }
+                    if key == "page"
+            ));
+        }
+
+        assert_eq!(
+            to_pairs(&Listing {
+                page: 2,
+                offset: -1
+            })
+            .unwrap(),
+            [("page", "2"), ("offset", "-1")].map(|(k, v)| (k.to_owned(), v.to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_with_hook() {
+        let mut clamped = None;
+        let value: u8 = super::deserialize_with_hook(
+            crate::ValueDeserializer {
+                key: None,
+                value: &"300".into(),
+            },
+            |value, ty| clamped = Some((value.to_owned(), ty)),
+        )
+        .unwrap();
+        assert_eq!(value, u8::MAX);
+        assert_eq!(clamped, Some(("300".to_owned(), "u8")));
+
+        let mut clamped = false;
+        let value: u8 = super::deserialize_with_hook(
+            crate::ValueDeserializer {
+                key: None,
+                value: &"30".into(),
+            },
+            |_, _| clamped = true,
+        )
+        .unwrap();
+        assert_eq!(value, 30);
+        assert!(!clamped);
+    }
+}
@@ -0,0 +1,110 @@
+use schemars::{JsonSchema, Schema};
+use serde_json::Value;
+
+/// Returns a JSON schema of the params `T` is deserialized from, for contract tests or
+/// generating clients.
+///
+/// It is the schema of `T` as an object, except that the schema of each param is inlined rather
+/// than a reference and isn't nullable, since a param is either given or missing.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use schemars::JsonSchema;
+///
+/// #[derive(Deserialize, JsonSchema)]
+/// struct Listing {
+///     owner: String,
+///     page: Option<u32>,
+/// }
+///
+/// let schema = matchit_serde::schema_for_params::<Listing>();
+/// assert_eq!(schema.get("required").unwrap(), &serde_json::json!(["owner"]));
+/// assert_eq!(schema.pointer("/properties/page/type").unwrap(), "integer");
+/// assert_eq!(schema.pointer("/properties/page/format").unwrap(), "uint32");
+/// ```
+pub fn schema_for_params<T: JsonSchema>() -> Schema {
+    let mut schema = schemars::schema_for!(T);
+    let root = schema.as_value().clone();
+    let properties = schema
+        .get_mut("properties")
+        .and_then(|properties| properties.as_object_mut());
+    for property in properties
+        .into_iter()
+        .flat_map(|properties| properties.values_mut())
+    {
+        let mut param = resolve(&root, property).clone();
+        if let Some(Value::Array(types)) = param.get_mut("type") {
+            types.retain(|ty| ty != "null");
+            if types.len() == 1 {
+                param["type"] = types.remove(0);
+            }
+        }
+        *property = param;
+    }
+    schema
+}
+
+/// Follows a `$defs` reference, or the only non-null alternative of an `anyOf` or `oneOf`.
+pub(crate) fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    if let Some(name) = schema
+        .get("$ref")
+        .and_then(|r| r.as_str())
+        .and_then(|r| r.strip_prefix("#/$defs/"))
+    {
+        return match root.get("$defs").and_then(|defs| defs.get(name)) {
+            Some(def) => resolve(root, def),
+            None => schema,
+        };
+    }
+    let alternatives = schema.get("anyOf").or_else(|| schema.get("oneOf"));
+    if let Some(alternatives) = alternatives.and_then(|a| a.as_array()) {
+        let mut non_null = alternatives
+            .iter()
+            .filter(|alternative| alternative.get("type").and_then(|t| t.as_str()) != Some("null"));
+        if let (Some(alternative), None) = (non_null.next(), non_null.next()) {
+            return resolve(root, alternative);
+        }
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[allow(dead_code)]
+    #[derive(Deserialize, JsonSchema)]
+    #[serde(rename_all = "lowercase")]
+    enum State {
+        Open,
+        Closed,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Deserialize, JsonSchema)]
+    struct Filter {
+        #[serde(rename = "repo-id")]
+        repo_id: u64,
+        state: State,
+        since: Option<State>,
+    }
+
+    #[test]
+    fn test_schema_for_params() {
+        let schema = schema_for_params::<Filter>();
+        assert_eq!(
+            schema.get("required").unwrap(),
+            &json!(["repo-id", "state"])
+        );
+        assert_eq!(
+            schema.get("properties").unwrap(),
+            &json!({
+                "repo-id": { "type": "integer", "format": "uint64", "minimum": 0 },
+                "state": { "type": "string", "enum": ["open", "closed"] },
+                "since": { "type": "string", "enum": ["open", "closed"] },
+            })
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! `#[serde(with)]` helpers for [`Version`](::semver::Version) and
+//! [`VersionReq`](::semver::VersionReq) params that may carry a leading `v`, like `/api/v1.2.0`.
+//!
+//! Without the prefix, both types can be used as params directly.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use semver::Version;
+//!
+//! #[derive(Deserialize)]
+//! struct Api {
+//!     #[serde(with = "matchit_serde::semver")]
+//!     version: Version,
+//! }
+//!
+//! let api: Api = matchit_serde::from_slice(&[("version", "v1.2.0")]).unwrap();
+//! assert_eq!(api.version, Version::new(1, 2, 0));
+//! ```
+
+use crate::parse::ParseVisitor;
+use core::{fmt::Display, marker::PhantomData, str::FromStr};
+use serde::{Deserializer, Serializer};
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |value| value.strip_prefix('v').unwrap_or(value).parse().ok(),
+        expecting: "a semantic version with an optional leading `v`",
+        marker: PhantomData,
+    })
+}
+
+/// Serializes the value without the leading `v`.
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice};
+    use semver::{Version, VersionReq};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Api {
+        #[serde(with = "super")]
+        version: Version,
+        #[serde(with = "super")]
+        req: VersionReq,
+    }
+
+    #[test]
+    fn test_versions() {
+        for version in ["1.2.3-rc.1", "v1.2.3-rc.1"] {
+            let api: Api = from_slice(&[("version", version), ("req", "v1.2")]).unwrap();
+            assert_eq!(api.version, Version::parse("1.2.3-rc.1").unwrap());
+            assert!(api.req.matches(&Version::new(1, 4, 0)));
+        }
+
+        let version: Version = from_slice(&[("version", "2.0.0")]).unwrap();
+        assert_eq!(version, Version::new(2, 0, 0));
+
+        let err = from_slice::<Api>(&[("version", "vv1.2.3"), ("req", "1")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "version"
+        ));
+    }
+}
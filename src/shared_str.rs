@@ -0,0 +1,73 @@
+//! `#[serde(with)]` helpers for `Arc<str>`, `Rc<str>` and `Box<str>` fields, built right from
+//! the borrowed value instead of going through an intermediate `String` like serde's own impls.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! # use std::sync::Arc;
+//! #[derive(Deserialize)]
+//! struct Route {
+//!     #[serde(with = "matchit_serde::shared_str")]
+//!     tenant: Arc<str>,
+//! }
+//!
+//! let route: Route = matchit_serde::from_slice(&[("tenant", "acme")]).unwrap();
+//! assert_eq!(&*route.tenant, "acme");
+//! ```
+
+use crate::parse::ParseVisitor;
+use core::marker::PhantomData;
+use serde::{Deserializer, Serializer};
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: for<'a> From<&'a str>,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |value| Some(T::from(value)),
+        expecting: "a string",
+        marker: PhantomData,
+    })
+}
+
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: AsRef<str>,
+{
+    serializer.serialize_str(value.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_slice, pairs::to_pairs};
+    use alloc::{boxed::Box, rc::Rc, sync::Arc};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Route {
+        #[serde(with = "super")]
+        tenant: Arc<str>,
+        #[serde(with = "super")]
+        user: Rc<str>,
+        #[serde(with = "super")]
+        post: Box<str>,
+    }
+
+    #[test]
+    fn test_shared_str() {
+        let route: Route =
+            from_slice(&[("tenant", "acme"), ("user", "j%C3%BCrgen"), ("post", "1")]).unwrap();
+        assert_eq!(&*route.tenant, "acme");
+        assert_eq!(&*route.user, "jürgen");
+        assert_eq!(&*route.post, "1");
+        assert_eq!(
+            to_pairs(&route).unwrap(),
+            [
+                ("tenant".to_owned(), "acme".to_owned()),
+                ("user".to_owned(), "jürgen".to_owned()),
+                ("post".to_owned(), "1".to_owned()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{Conflict, Params, ParamsDeserializationError, ParamsDeserializer};
+use alloc::{borrow::Cow, vec::Vec};
+use core::str::Utf8Error;
+use percent_encoding::percent_decode_str;
+use serde::de;
+
+/// How the keys and values of a source are decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Decoding {
+    /// Use keys and values as they are.
+    None,
+    /// Percent-decode keys and values, like path params.
+    #[default]
+    Percent,
+    /// Decode `+` as a space and percent-decode keys and values, like form-urlencoded query
+    /// strings.
+    Form,
+}
+
+impl Decoding {
+    pub(crate) fn decode(self, raw: &str) -> Result<Cow<'_, str>, Utf8Error> {
+        match self {
+            Self::None => Ok(Cow::Borrowed(raw)),
+            Self::Percent => percent_decode_str(raw).decode_utf8(),
+            Self::Form if raw.contains('+') => {
+                let raw = raw.replace('+', " ");
+                Ok(Cow::Owned(
+                    percent_decode_str(&raw).decode_utf8()?.into_owned(),
+                ))
+            }
+            Self::Form => percent_decode_str(raw).decode_utf8(),
+        }
+    }
+}
+
+/// Combines several sources of key/value pairs, such as path params, the query string and
+/// defaults, into one set of [`Params`].
+///
+/// Sources are added in order of precedence: if multiple sources contain the same key, the value
+/// of the source added first is used.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::{Decoding, Sources};
+///
+/// #[derive(Deserialize)]
+/// struct Search {
+///     user: String,
+///     page: u32,
+///     limit: u32,
+/// }
+///
+/// let search: Search = Sources::new()
+///     .add([("user", "alice%20b")], Decoding::Percent)
+///     .add([("page", "2"), ("user", "bob")], Decoding::Form)
+///     .add([("page", "1"), ("limit", "20")], Decoding::None)
+///     .deserialize()
+///     .unwrap();
+/// assert_eq!(search.user, "alice b");
+/// assert_eq!(search.page, 2);
+/// assert_eq!(search.limit, 20);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Sources<'de> {
+    sources: Vec<Source<'de>>,
+}
+
+#[derive(Debug, Clone)]
+enum Source<'de> {
+    Raw(Vec<(&'de str, &'de str)>, Decoding),
+    #[cfg(feature = "query")]
+    Decoded(Params<'de>),
+}
+
+impl<'de> Sources<'de> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a source with a lower precedence than all previously added ones.
+    pub fn add(
+        mut self,
+        pairs: impl IntoIterator<Item = (&'de str, &'de str)>,
+        decoding: Decoding,
+    ) -> Self {
+        self.sources
+            .push(Source::Raw(pairs.into_iter().collect(), decoding));
+        self
+    }
+
+    /// Adds a form-urlencoded query string, parsed like [`Params::from_query`], as a source with
+    /// a lower precedence than all previously added ones.
+    ///
+    /// ```
+    /// use matchit_serde::Sources;
+    ///
+    /// let page: (u32,) = Sources::new().add_query("?page=2").deserialize().unwrap();
+    /// assert_eq!(page, (2,));
+    /// ```
+    #[cfg(feature = "query")]
+    pub fn add_query(mut self, query: &'de str) -> Self {
+        self.sources
+            .push(Source::Decoded(Params::from_query(query)));
+        self
+    }
+
+    /// Decodes and merges the sources.
+    pub fn into_params(self) -> Result<Params<'de>, ParamsDeserializationError> {
+        let mut params = Params(Vec::new());
+        for source in self.sources {
+            let source = match source {
+                Source::Raw(pairs, decoding) => Params(
+                    pairs
+                        .into_iter()
+                        .map(|(k, v)| Ok((decoding.decode(k)?, decoding.decode(v)?)))
+                        .collect::<Result<Vec<_>, Utf8Error>>()?,
+                ),
+                #[cfg(feature = "query")]
+                Source::Decoded(params) => params,
+            };
+            params.merge(source, Conflict::Keep)?;
+        }
+        Ok(params)
+    }
+
+    /// Deserializes `T` from the merged sources.
+    pub fn deserialize<T>(self) -> Result<T, ParamsDeserializationError>
+    where
+        T: de::Deserialize<'de>,
+    {
+        ParamsDeserializer::new(self.into_params()?).deserialize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoding() {
+        assert_eq!(Decoding::None.decode("a+b%20").unwrap(), "a+b%20");
+        assert_eq!(Decoding::Percent.decode("a+b%20").unwrap(), "a+b ");
+        assert_eq!(Decoding::Form.decode("a+b%20").unwrap(), "a b ");
+        assert_eq!(Decoding::Form.decode("%2B").unwrap(), "+");
+    }
+
+    #[test]
+    fn test_precedence() {
+        let params = Sources::new()
+            .add([("id", "1")], Decoding::Percent)
+            .add([("id", "2"), ("tag", "a+b")], Decoding::Form)
+            .add([("id", "3"), ("limit", "10")], Decoding::None)
+            .into_params()
+            .unwrap();
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("id", "1"), ("tag", "a b"), ("limit", "10")]
+        );
+    }
+
+    #[cfg(feature = "query")]
+    #[test]
+    fn test_add_query() {
+        let params = Sources::new()
+            .add([("id", "1")], Decoding::Percent)
+            .add_query("?id=2&tag=a+b&flag&&q%5B%5D=x")
+            .into_params()
+            .unwrap();
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("id", "1"), ("tag", "a b"), ("flag", ""), ("q[]", "x")]
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! `#[serde(with)]` helpers decoding [Sqids](https://sqids.org) params, the successor of
+//! Hashids, into real numeric IDs.
+//!
+//! The [`Sqids`] instance is configured at runtime with [`configure`], and params fail to
+//! decode until it is called. A shuffled custom alphabet takes the role of a Hashids salt. Only canonical IDs are accepted, so every number
+//! has exactly one public ID.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use sqids::Sqids;
+//!
+//! #[derive(Deserialize)]
+//! struct Invoice {
+//!     #[serde(with = "matchit_serde::sqids")]
+//!     id: u64,
+//!     #[serde(with = "matchit_serde::sqids::many")]
+//!     lines: Vec<u64>,
+//! }
+//!
+//! let sqids = Sqids::default();
+//! let (id, lines) = (sqids.encode(&[1]).unwrap(), sqids.encode(&[1, 2, 3]).unwrap());
+//! matchit_serde::sqids::configure(sqids).unwrap();
+//!
+//! let invoice: Invoice = matchit_serde::from_slice(&[("id", &*id), ("lines", &*lines)]).unwrap();
+//! assert_eq!(invoice.id, 1);
+//! assert_eq!(invoice.lines, [1, 2, 3]);
+//! ```
+
+use crate::parse::ParseVisitor;
+use ::sqids::Sqids;
+use core::marker::PhantomData;
+use serde::{Deserializer, Serializer, de, ser};
+use std::sync::OnceLock;
+
+static SQIDS: OnceLock<Sqids> = OnceLock::new();
+
+/// Sets the [`Sqids`] used by the helpers, giving it back if one is already set.
+pub fn configure(sqids: Sqids) -> Result<(), Sqids> {
+    SQIDS.set(sqids)
+}
+
+const UNCONFIGURED: &str = "matchit_serde::sqids::configure was not called";
+
+fn decode(value: &str) -> Option<Vec<u64>> {
+    let sqids = SQIDS.get()?;
+    let numbers = sqids.decode(value);
+    let canonical = sqids.encode(&numbers).ok()?;
+    (!numbers.is_empty() && canonical == value).then_some(numbers)
+}
+
+fn encode<S>(numbers: &[u64], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let sqids = SQIDS
+        .get()
+        .ok_or_else(|| ser::Error::custom(UNCONFIGURED))?;
+    let value = sqids.encode(numbers).map_err(ser::Error::custom)?;
+    serializer.serialize_str(&value)
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    SQIDS.get().ok_or_else(|| de::Error::custom(UNCONFIGURED))?;
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |value| match decode(value)?.as_slice() {
+            [number] => Some(*number),
+            _ => None,
+        },
+        expecting: "a sqid of a single number",
+        marker: PhantomData,
+    })
+}
+
+pub fn serialize<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    encode(&[*value], serializer)
+}
+
+/// A sqid of one or more numbers as [`Vec<u64>`].
+pub mod many {
+    use crate::parse::ParseVisitor;
+    use core::marker::PhantomData;
+    use serde::{Deserializer, Serializer, de};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::SQIDS
+            .get()
+            .ok_or_else(|| de::Error::custom(super::UNCONFIGURED))?;
+        deserializer.deserialize_str(ParseVisitor {
+            parse: super::decode,
+            expecting: "a sqid",
+            marker: PhantomData,
+        })
+    }
+
+    pub fn serialize<S>(value: &[u64], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::encode(value, serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use serde::{Deserialize, Serialize};
+    use sqids::Sqids;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Invoice {
+        #[serde(with = "super")]
+        id: u64,
+        #[serde(with = "super::many")]
+        lines: Vec<u64>,
+    }
+
+    #[test]
+    fn test_sqids() {
+        let sqids = Sqids::builder()
+            .alphabet(
+                "k3G7QAe51FCsPW92uEOyq4Bg6Sp8YzVTmnU0liwDdHXLajZrfxNhobJIRcMvKt"
+                    .chars()
+                    .collect(),
+            )
+            .build()
+            .unwrap();
+        let (id, lines) = (sqids.encode(&[42]).unwrap(), sqids.encode(&[1, 2]).unwrap());
+        let err = from_slice::<Invoice>(&[("id", &*id), ("lines", &*lines)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "matchit_serde::sqids::configure was not called"
+        );
+        super::configure(sqids).unwrap();
+
+        let invoice: Invoice = from_slice(&[("id", &*id), ("lines", &*lines)]).unwrap();
+        assert_eq!(invoice.id, 42);
+        assert_eq!(invoice.lines, [1, 2]);
+        assert_eq!(
+            to_pairs(&invoice).unwrap(),
+            [
+                ("id".to_owned(), id.clone()),
+                ("lines".to_owned(), lines.clone())
+            ]
+        );
+
+        for bad in [&*lines, "", "not a sqid"] {
+            let err = from_slice::<Invoice>(&[("id", bad), ("lines", &*lines)]).unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "id"
+            ));
+        }
+    }
+}
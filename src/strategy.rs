@@ -0,0 +1,73 @@
+//! [proptest](https://docs.rs/proptest) strategies for generating params and matched routes.
+
+use crate::ParamsBuf;
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+use proptest::{collection::btree_map, prelude::*};
+use std::{format, string::String, vec::Vec};
+
+/// Characters that have to be encoded in a path segment so it round-trips through matchit.
+const SEGMENT: &AsciiSet = &CONTROLS.add(b'/').add(b'%').add(b' ').add(b'?').add(b'#');
+
+/// Keys which are valid in matchit templates.
+pub fn key() -> impl Strategy<Value = String> {
+    "[a-z_][a-z0-9_]{0,15}"
+}
+
+/// Non-empty decoded values, biased towards strings that are easy to mis-decode.
+pub fn value() -> impl Strategy<Value = String> {
+    prop_oneof![
+        3 => any::<String>().prop_filter("empty value", |value| !value.is_empty()),
+        1 => prop::sample::select(&[
+            "%", "%25", "%2F", "%zz", "+", " ", "/", "a/b", "ü", "🦀", "{id}", "?", "#",
+        ][..])
+        .prop_map(String::from),
+    ]
+}
+
+/// Params with unique keys and arbitrary non-empty values.
+pub fn params_buf() -> impl Strategy<Value = ParamsBuf> {
+    btree_map(key(), value(), 0..8).prop_map(|params| params.into_iter().collect::<Vec<_>>().into())
+}
+
+/// A route template, a percent-encoded path matching it and the params the path decodes to.
+pub fn route() -> impl Strategy<Value = (String, String, ParamsBuf)> {
+    btree_map(key(), value(), 1..8).prop_map(|params| {
+        let mut template = String::new();
+        let mut path = String::new();
+        for (key, value) in &params {
+            template.push_str(&format!("/{{{key}}}"));
+            path.push('/');
+            path.extend(utf8_percent_encode(value, SEGMENT));
+        }
+        let params = params.into_iter().collect::<Vec<_>>().into();
+        (template, path, params)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    proptest! {
+        #[test]
+        fn test_route_roundtrip((template, path, expected) in route()) {
+            let params = crate::testing::params(&template, &path);
+            prop_assert_eq!(&params, &expected);
+
+            let map: BTreeMap<String, String> = params.deserialize().unwrap();
+            let expected = expected.as_params();
+            prop_assert_eq!(map.len(), expected.len());
+            for (key, value) in expected.iter() {
+                prop_assert_eq!(map.get(key).map(String::as_str), Some(value));
+            }
+        }
+
+        #[test]
+        fn test_params_buf_serialize_roundtrip(params in params_buf()) {
+            let json = serde_json::to_string(&params).unwrap();
+            let map: BTreeMap<String, String> = serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(map.len(), params.as_params().len());
+        }
+    }
+}
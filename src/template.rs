@@ -0,0 +1,54 @@
+use alloc::{string::String, vec::Vec};
+
+/// Returns the names of the params of a matchit route template like `/{owner}/{*path}` in order.
+pub(crate) fn params(template: &str) -> Vec<&str> {
+    let mut params = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if rest[start + 1..].starts_with('{') {
+            rest = &rest[start + 2..];
+            continue;
+        }
+        let Some(len) = rest[start + 1..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + 1 + len];
+        params.push(name.strip_prefix('*').unwrap_or(name));
+        rest = &rest[start + 2 + len..];
+    }
+    params
+}
+
+/// Converts a matchit route template to an OpenAPI path, turning `{*path}` into `{path}` and
+/// unescaping `{{` and `}}`.
+pub(crate) fn openapi_path(template: &str) -> String {
+    let mut path = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) | ('{', Some('*')) => {
+                chars.next();
+                path.push(c);
+            }
+            _ => path.push(c),
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template() {
+        assert_eq!(
+            params("/{owner}/{{raw}}/{repo}/{*path}"),
+            ["owner", "repo", "path"]
+        );
+        assert_eq!(
+            openapi_path("/{owner}/{{raw}}/{*path}"),
+            "/{owner}/{raw}/{path}"
+        );
+    }
+}
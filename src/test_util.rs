@@ -0,0 +1,16 @@
+//! Helpers shared by the tests of the framework adapters.
+
+use core::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+/// Polls `future` once and returns its output, for futures which never wait, like the ones of
+/// extractors and of handlers that don't await.
+pub(crate) fn poll_once<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("the future waited"),
+    }
+}
@@ -0,0 +1,153 @@
+//! Helpers for testing route param structs without setting up a router by hand.
+
+use crate::{ParamsBuf, ParamsDeserializationError};
+use core::fmt::Debug;
+use serde::de::DeserializeOwned;
+use std::fmt::Write;
+
+/// Matches `path` against a router containing only `template` and returns the matched params.
+///
+/// # Panics
+///
+/// Panics if the template is invalid or doesn't match the path.
+#[track_caller]
+pub fn params(template: &str, path: &str) -> ParamsBuf {
+    let mut router = matchit::Router::new();
+    if let Err(err) = router.insert(template, ()) {
+        panic!("invalid route template {template:?}: {err}");
+    }
+    let matched = match router.at(path) {
+        Ok(matched) => matched,
+        Err(err) => panic!("path {path:?} doesn't match template {template:?}: {err}"),
+    };
+    match ParamsBuf::try_from(&matched.params) {
+        Ok(params) => params,
+        Err(err) => panic!("failed to decode params of path {path:?}: {err}"),
+    }
+}
+
+/// Deserializes `T` from the params of `path` matched against `template`.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`params`].
+#[track_caller]
+pub fn deserialize<T>(template: &str, path: &str) -> Result<T, ParamsDeserializationError>
+where
+    T: DeserializeOwned,
+{
+    params(template, path).deserialize()
+}
+
+/// Asserts that the params of `path` matched against `template` deserialize into `expected`.
+///
+/// Prefer the [`assert_deserializes!`](crate::assert_deserializes) macro.
+#[track_caller]
+pub fn assert_deserializes<T>(template: &str, path: &str, expected: &T)
+where
+    T: DeserializeOwned + PartialEq + Debug,
+{
+    let actual: T = match deserialize(template, path) {
+        Ok(actual) => actual,
+        Err(err) => panic!(
+            "failed to deserialize path {path:?} with template {template:?}: {err}\nexpected: {expected:#?}"
+        ),
+    };
+    if actual != *expected {
+        let diff = diff(&format!("{expected:#?}"), &format!("{actual:#?}"));
+        panic!(
+            "path {path:?} with template {template:?} didn't deserialize into the expected value\n--- expected\n+++ actual\n{diff}"
+        );
+    }
+}
+
+/// Asserts that the params of `path` matched against `template` deserialize into `expected`,
+/// printing a line diff of both values on failure.
+///
+/// ```
+/// # use serde::Deserialize;
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct User {
+///     id: u32,
+/// }
+///
+/// matchit_serde::assert_deserializes!("/users/{id}", "/users/3", User { id: 3 });
+/// ```
+#[macro_export]
+macro_rules! assert_deserializes {
+    ($template:expr, $path:expr, $expected:expr $(,)?) => {
+        $crate::testing::assert_deserializes($template, $path, &$expected)
+    };
+}
+
+/// Line diff of `expected` and `actual` based on their longest common subsequence.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+
+    // lcs[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            let _ = writeln!(out, " {}", expected[i]);
+            i += 1;
+            j += 1;
+        } else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            let _ = writeln!(out, "-{}", expected[i]);
+            i += 1;
+        } else {
+            let _ = writeln!(out, "+{}", actual[j]);
+            j += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Path {
+        principal: String,
+        id: u32,
+    }
+
+    #[test]
+    fn test_assert_deserializes() {
+        crate::assert_deserializes!(
+            "/{principal}/{id}",
+            "/user%20a/3",
+            Path {
+                principal: "user a".to_owned(),
+                id: 3,
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "-    id: 4,\n+    id: 3,")]
+    fn test_assert_deserializes_diff() {
+        crate::assert_deserializes!(
+            "/{principal}/{id}",
+            "/user/3",
+            Path {
+                principal: "user".to_owned(),
+                id: 4,
+            }
+        );
+    }
+}
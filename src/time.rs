@@ -0,0 +1,135 @@
+//! `#[serde(with)]` helpers for `time` params.
+//!
+//! [`Date`](::time::Date) params deserialize from `YYYY-MM-DD` without a helper. The formats
+//! `time` uses for [`OffsetDateTime`](::time::OffsetDateTime) and
+//! [`PrimitiveDateTime`](::time::PrimitiveDateTime) by default contain spaces, so
+//! these helpers use RFC 3339 and ISO 8601 instead.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use time::{Date, OffsetDateTime, PrimitiveDateTime};
+//!
+//! #[derive(Deserialize)]
+//! struct Range {
+//!     day: Date,
+//!     #[serde(with = "matchit_serde::time::offset_date_time")]
+//!     from: OffsetDateTime,
+//!     #[serde(with = "matchit_serde::time::primitive_date_time")]
+//!     to: PrimitiveDateTime,
+//! }
+//!
+//! let range: Range = matchit_serde::from_slice(&[
+//!     ("day", "2024-02-29"),
+//!     ("from", "2024-02-29T12:30:00Z"),
+//!     ("to", "2024-02-29T14:30:00"),
+//! ])
+//! .unwrap();
+//! assert_eq!(range.from.date(), range.day);
+//! assert_eq!(range.to.hour(), 14);
+//! ```
+
+/// RFC 3339 [`OffsetDateTime`](::time::OffsetDateTime), like `2024-02-29T12:30:00+02:00`.
+pub mod offset_date_time {
+    use crate::parse::ParseVisitor;
+    use ::time::{OffsetDateTime, format_description::well_known::Rfc3339};
+    use core::marker::PhantomData;
+    use serde::{Deserializer, Serializer, ser};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<OffsetDateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ParseVisitor {
+            parse: |value| OffsetDateTime::parse(value, &Rfc3339).ok(),
+            expecting: "an RFC 3339 date and time",
+            marker: PhantomData,
+        })
+    }
+
+    pub fn serialize<S>(value: &OffsetDateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let value = value.format(&Rfc3339).map_err(ser::Error::custom)?;
+        serializer.serialize_str(&value)
+    }
+}
+
+/// ISO 8601 [`PrimitiveDateTime`](::time::PrimitiveDateTime) without offset, like
+/// `2024-02-29T12:30:00` or `2024-02-29T12:30:00.25`.
+pub mod primitive_date_time {
+    use crate::parse::ParseVisitor;
+    use ::time::{PrimitiveDateTime, format_description::FormatItem, macros::format_description};
+    use core::marker::PhantomData;
+    use serde::{Deserializer, Serializer, ser};
+
+    const FORMAT: &[FormatItem<'_>] = format_description!(
+        "[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]"
+    );
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<PrimitiveDateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ParseVisitor {
+            parse: |value| PrimitiveDateTime::parse(value, FORMAT).ok(),
+            expecting: "an ISO 8601 date and time without offset",
+            marker: PhantomData,
+        })
+    }
+
+    pub fn serialize<S>(value: &PrimitiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let value = value.format(FORMAT).map_err(ser::Error::custom)?;
+        serializer.serialize_str(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use serde::{Deserialize, Serialize};
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, macros::datetime};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Range {
+        day: Date,
+        #[serde(with = "super::offset_date_time")]
+        from: OffsetDateTime,
+        #[serde(with = "super::primitive_date_time")]
+        to: PrimitiveDateTime,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let range: Range = from_slice(&[
+            ("day", "2024-02-29"),
+            ("from", "2024-02-29T14:30:00%2B02:00"),
+            ("to", "2024-02-29T14:30:00.5"),
+        ])
+        .unwrap();
+        assert_eq!(range.from, datetime!(2024-02-29 12:30 UTC));
+        assert_eq!(range.to, datetime!(2024-02-29 14:30:00.5));
+
+        let pairs = to_pairs(&range).unwrap();
+        assert_eq!(pairs[1].1, "2024-02-29T14:30:00+02:00");
+        assert_eq!(pairs[2].1, "2024-02-29T14:30:00.5");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = from_slice::<Range>(&[
+            ("day", "2024-02-29"),
+            ("from", "2024-02-29 12:30:00"),
+            ("to", "2024-02-29T14:30:00"),
+        ])
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                if key == "from" && expected_type.ends_with("OffsetDateTime")
+        ));
+    }
+}
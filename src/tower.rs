@@ -0,0 +1,344 @@
+//! Glue between a [`matchit::Router`] and the tower ecosystem.
+
+use crate::{Params, ParamsBuf, ParamsDeserializationError, ParamsDeserializer};
+use alloc::{boxed::Box, string::String, sync::Arc};
+use core::task::{Context, Poll};
+use http::{Extensions, Request};
+use serde::de::DeserializeOwned;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A tower service routing requests to per-route services with a [`matchit::Router`].
+///
+/// The params of the matched route are stored in the request extensions as a [`ParamsBuf`], along
+/// with the request path under [`RAW_PATH`](crate::RAW_PATH), so the route services can
+/// deserialize them when needed, for example with [`ExtensionParams`](crate::ExtensionParams). Requests which don't match any route, or whose
+/// params aren't valid UTF-8 after percent-decoding, are passed to the fallback service.
+///
+/// The route services are cloned for every request, like axum does, so [`Service::poll_ready`]
+/// always returns `Poll::Ready(Ok(()))` and the services have to be ready whenever they are
+/// called.
+///
+/// ```
+/// use matchit_serde::tower::MatchitRouterService;
+/// # #[derive(Clone)]
+/// # struct Handler;
+/// # impl tower_service::Service<http::Request<()>> for Handler {
+/// #     type Response = ();
+/// #     type Error = core::convert::Infallible;
+/// #     type Future = core::future::Ready<Result<(), Self::Error>>;
+/// #     fn poll_ready(
+/// #         &mut self,
+/// #         _: &mut core::task::Context<'_>,
+/// #     ) -> core::task::Poll<Result<(), Self::Error>> {
+/// #         core::task::Poll::Ready(Ok(()))
+/// #     }
+/// #     fn call(&mut self, _: http::Request<()>) -> Self::Future {
+/// #         core::future::ready(Ok(()))
+/// #     }
+/// # }
+/// # let (users, posts, not_found) = (Handler, Handler, Handler);
+///
+/// let service = MatchitRouterService::new(not_found)
+///     .route("/users/{user}", users)
+///     .unwrap()
+///     .route("/users/{user}/posts/{post}", posts)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct MatchitRouterService<S> {
+    router: Arc<matchit::Router<S>>,
+    fallback: S,
+}
+
+impl<S> MatchitRouterService<S>
+where
+    S: Clone,
+{
+    /// Creates a router without routes, passing all requests to `fallback`.
+    pub fn new(fallback: S) -> Self {
+        Self {
+            router: Arc::new(matchit::Router::new()),
+            fallback,
+        }
+    }
+
+    /// Adds a route handled by `service`.
+    pub fn route(
+        mut self,
+        template: impl Into<String>,
+        service: S,
+    ) -> Result<Self, matchit::InsertError> {
+        Arc::make_mut(&mut self.router).insert(template, service)?;
+        Ok(self)
+    }
+}
+
+impl<S, B> Service<Request<B>> for MatchitRouterService<S>
+where
+    S: Service<Request<B>> + Clone,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let matched = self.router.at(req.uri().path()).ok().and_then(|matched| {
+            let params = ParamsBuf::try_from(&matched.params)
+                .ok()?
+                .with_raw_path(req.uri().path());
+            Some((matched.value.clone(), params))
+        });
+        match matched {
+            Some((mut service, params)) => {
+                req.extensions_mut().insert(params);
+                service.call(req)
+            }
+            None => self.fallback.clone().call(req),
+        }
+    }
+}
+
+type InsertParams = dyn Fn(&matchit::Params<'_, '_>, &str, &mut Extensions) -> Result<(), ParamsDeserializationError>
+    + Send
+    + Sync;
+
+/// A layer deserializing the params of a request into a per-route type and storing it in the
+/// request extensions.
+///
+/// The request path is matched against the registered templates. If the params can't be
+/// deserialized, the [`ParamsDeserializationError`] is stored in the extensions instead, so the
+/// handler can respond with it. Requests which don't match any template are passed on unchanged.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::tower::TypedParamsLayer;
+///
+/// #[derive(Clone, Deserialize)]
+/// struct UserParams {
+///     id: u32,
+/// }
+///
+/// let layer = TypedParamsLayer::new()
+///     .route::<UserParams>("/users/{id}")
+///     .unwrap();
+/// // handlers read the params with `req.extensions().get::<UserParams>()` or
+/// // `matchit_serde::get_typed_params::<UserParams>(req.extensions())`
+/// ```
+#[derive(Clone, Default)]
+pub struct TypedParamsLayer {
+    router: Arc<matchit::Router<Arc<InsertParams>>>,
+}
+
+impl TypedParamsLayer {
+    /// Creates a layer without routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deserializes the params of requests matching `template` into `T`.
+    pub fn route<T>(mut self, template: impl Into<String>) -> Result<Self, matchit::InsertError>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let template: String = template.into();
+        let route = template.clone();
+        let insert: Box<InsertParams> = Box::new(move |params, path, extensions| {
+            let params = Params::try_from(params)?.with_raw_path(path);
+            let params = ParamsDeserializer::new(params).deserialize_route::<T>(&route)?;
+            extensions.insert(params);
+            Ok(())
+        });
+        Arc::make_mut(&mut self.router).insert(template, Arc::from(insert))?;
+        Ok(self)
+    }
+}
+
+impl core::fmt::Debug for TypedParamsLayer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TypedParamsLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for TypedParamsLayer {
+    type Service = TypedParams<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TypedParams {
+            layer: self.clone(),
+            inner,
+        }
+    }
+}
+
+/// The service created by [`TypedParamsLayer`].
+#[derive(Debug, Clone)]
+pub struct TypedParams<S> {
+    layer: TypedParamsLayer,
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for TypedParams<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let (mut parts, body) = req.into_parts();
+        if let Ok(matched) = self.layer.router.at(parts.uri.path())
+            && let Err(err) =
+                (matched.value)(&matched.params, parts.uri.path(), &mut parts.extensions)
+        {
+            parts.extensions.insert(err);
+        }
+        self.inner.call(Request::from_parts(parts, body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MatchitRouterService, TypedParamsLayer};
+    use crate::{ExtensionParams, ParamsDeserializationError, test_util::poll_once};
+    use core::{
+        convert::Infallible,
+        future::{Ready, ready},
+        task::{Context, Poll},
+    };
+    use http::Request;
+    use tower_layer::Layer;
+    use tower_service::Service;
+
+    #[derive(Clone)]
+    struct Echo(&'static str);
+
+    #[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+    struct User {
+        id: Option<String>,
+    }
+
+    impl Service<Request<()>> for Echo {
+        type Response = String;
+        type Error = Infallible;
+        type Future = Ready<Result<String, Infallible>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: Request<()>) -> Self::Future {
+            let id = ExtensionParams::<User>::from_extensions(req.extensions())
+                .ok()
+                .and_then(|user| user.0.id);
+            ready(Ok(format!("{} {id:?}", self.0)))
+        }
+    }
+
+    fn call(service: &mut MatchitRouterService<Echo>, path: &str) -> String {
+        let Ok(body) = poll_once(service.call(Request::get(path).body(()).unwrap()));
+        body
+    }
+
+    #[test]
+    fn test_routing() {
+        let mut service = MatchitRouterService::new(Echo("fallback"))
+            .route("/users/{id}", Echo("user"))
+            .unwrap()
+            .route("/about", Echo("about"))
+            .unwrap();
+
+        assert_eq!(call(&mut service, "/users/a%20b"), "user Some(\"a b\")");
+        assert_eq!(call(&mut service, "/about"), "about None");
+        assert_eq!(call(&mut service, "/missing"), "fallback None");
+        assert_eq!(call(&mut service, "/users/%FF"), "fallback None");
+    }
+
+    #[derive(Clone)]
+    struct Extract;
+
+    impl Service<Request<()>> for Extract {
+        type Response = Request<()>;
+        type Error = Infallible;
+        type Future = Ready<Result<Request<()>, Infallible>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: Request<()>) -> Self::Future {
+            ready(Ok(req))
+        }
+    }
+
+    #[test]
+    fn test_typed_params_layer() {
+        let mut service = TypedParamsLayer::new()
+            .route::<User>("/users/{id}")
+            .unwrap()
+            .route::<(u32,)>("/posts/{id}")
+            .unwrap()
+            .layer(Extract);
+        let mut call = |path| {
+            let Ok(req) = poll_once(service.call(Request::get(path).body(()).unwrap()));
+            req
+        };
+
+        let req = call("/users/7");
+        assert_eq!(
+            req.extensions().get::<User>(),
+            Some(&User {
+                id: Some("7".to_owned())
+            })
+        );
+
+        let req = call("/posts/x");
+        assert!(req.extensions().get::<(u32,)>().is_none());
+        assert!(matches!(
+            req.extensions().get::<ParamsDeserializationError>(),
+            Some(ParamsDeserializationError::ParseErrorAtIndex { .. })
+        ));
+
+        assert!(call("/missing").extensions().is_empty());
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct File {
+        name: String,
+        #[serde(rename = "$raw_path")]
+        path: String,
+    }
+
+    #[test]
+    fn test_raw_path() {
+        let mut service = MatchitRouterService::new(Extract)
+            .route("/files/{name}", Extract)
+            .unwrap();
+        let Ok(req) = poll_once(service.call(Request::get("/files/a%20b").body(()).unwrap()));
+        let file = ExtensionParams::<File>::from_extensions(req.extensions()).unwrap();
+        assert_eq!(file.0.path, "/files/a%20b");
+
+        let mut service = TypedParamsLayer::new()
+            .route::<File>("/files/{name}")
+            .unwrap()
+            .layer(Extract);
+        let Ok(req) = poll_once(service.call(Request::get("/files/a%20b").body(()).unwrap()));
+        assert_eq!(
+            req.extensions().get::<File>(),
+            Some(&File {
+                name: "a b".to_owned(),
+                path: "/files/a%20b".to_owned(),
+            })
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! `#[serde(with)]` helpers for [`Ulid`] params.
+//!
+//! `Ulid`'s own impl checks the value only after deserializing it to a `String`, so its errors
+//! don't name the key. The value is parsed as case-insensitive Crockford base32.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use ulid::Ulid;
+//!
+//! #[derive(Deserialize)]
+//! struct Event {
+//!     #[serde(with = "matchit_serde::ulid")]
+//!     id: Ulid,
+//! }
+//!
+//! let event: Event =
+//!     matchit_serde::from_slice(&[("id", "01arz3ndektsv4rrffq69g5fav")]).unwrap();
+//! assert_eq!(event.id.to_string(), "01ARZ3NDEKTSV4RRFFQ69G5FAV");
+//! ```
+
+use crate::parse::ParseVisitor;
+use ::ulid::Ulid;
+use core::marker::PhantomData;
+use serde::{Deserializer, Serializer};
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Ulid, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(ParseVisitor {
+        parse: |value| Ulid::from_string(value).ok(),
+        expecting: "a 26 character Crockford base32 ULID",
+        marker: PhantomData,
+    })
+}
+
+pub fn serialize<S>(value: &Ulid, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice, pairs::to_pairs};
+    use serde::{Deserialize, Serialize};
+    use ulid::Ulid;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Event {
+        #[serde(with = "super")]
+        id: Ulid,
+    }
+
+    #[test]
+    fn test_ulids() {
+        let id = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+        for value in [id, "01arz3ndektsv4rrffq69g5fav"] {
+            let event: Event = from_slice(&[("id", value)]).unwrap();
+            assert_eq!(event.id, Ulid::from_string(id).unwrap());
+        }
+        let event: Event = from_slice(&[("id", id)]).unwrap();
+        assert_eq!(
+            to_pairs(&event).unwrap(),
+            [("id".to_owned(), id.to_owned())]
+        );
+
+        let err = from_slice::<Event>(&[("id", "01ARZ3NDEKTSV4RRFFQ69G5FAU0")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                if key == "id" && expected_type.ends_with("Ulid")
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+//! `url` params, typically captured by a catch-all like `/fetch/{*target}`.
+//!
+//! [`Url`] params deserialize without a helper after percent-decoding and must be absolute.
+//! Relative references like `/docs/intro?lang=en` can be accepted with
+//! [`relative`] instead, which rejects absolute URLs.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use url::Url;
+//!
+//! #[derive(Deserialize)]
+//! struct Fetch {
+//!     target: Url,
+//! }
+//!
+//! let fetch: Fetch =
+//!     matchit_serde::from_slice(&[("target", "https%3A%2F%2Fexample.com%2Fa%3Fb%3Dc")]).unwrap();
+//! assert_eq!(fetch.target.as_str(), "https://example.com/a?b=c");
+//! assert!(matchit_serde::from_slice::<Fetch>(&[("target", "/a")]).is_err());
+//! ```
+
+use ::url::{ParseError, Url};
+
+/// Relative URL references as [`String`], like `/docs/intro` or `../img.png?size=2`.
+pub mod relative {
+    use crate::parse::ParseVisitor;
+    use alloc::{borrow::ToOwned, string::String};
+    use core::marker::PhantomData;
+    use serde::{Deserializer, Serializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ParseVisitor {
+            parse: |value| super::is_relative(value).then(|| value.to_owned()),
+            expecting: "a relative URL",
+            marker: PhantomData,
+        })
+    }
+
+    pub fn serialize<S>(value: &str, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(value)
+    }
+}
+
+fn is_relative(value: &str) -> bool {
+    matches!(Url::parse(value), Err(ParseError::RelativeUrlWithoutBase))
+        && Url::parse("http://localhost/")
+            .and_then(|base| base.join(value))
+            .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamsDeserializationError, from_slice};
+    use alloc::string::String;
+    use serde::Deserialize;
+    use url::Url;
+
+    #[derive(Debug, Deserialize)]
+    struct Proxy {
+        target: Url,
+        #[serde(with = "super::relative")]
+        redirect: String,
+    }
+
+    #[test]
+    fn test_urls() {
+        let proxy: Proxy = from_slice(&[
+            ("target", "https://example.com/a%20b"),
+            ("redirect", "%2Fdocs%2Fintro%3Flang%3Den"),
+        ])
+        .unwrap();
+        assert_eq!(proxy.target.as_str(), "https://example.com/a%20b");
+        assert_eq!(proxy.redirect, "/docs/intro?lang=en");
+
+        let err = from_slice::<Proxy>(&[("target", "/a"), ("redirect", "/b")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type, .. }
+                if key == "target" && expected_type.ends_with("Url")
+        ));
+
+        let err =
+            from_slice::<Proxy>(&[("target", "http://a"), ("redirect", "http://b")]).unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "redirect"
+        ));
+    }
+}
@@ -0,0 +1,100 @@
+//! OpenAPI path parameters for param structs deriving [`ToSchema`](::utoipa::ToSchema).
+//!
+//! The parameters are built from the route template, so every param of the template is
+//! documented as a required path parameter, with the schema of the field of the same name.
+//!
+//! ```
+//! # use serde::Deserialize;
+//! use matchit_serde::utoipa::{path, path_parameters};
+//! use utoipa::ToSchema;
+//!
+//! #[derive(Deserialize, ToSchema)]
+//! struct FileParams {
+//!     owner: String,
+//!     id: u32,
+//!     path: String,
+//! }
+//!
+//! let template = "/{owner}/files/{id}/{*path}";
+//! let parameters = path_parameters::<FileParams>(template);
+//! assert_eq!(parameters.len(), 3);
+//! assert_eq!(parameters[1].name, "id");
+//! assert_eq!(path(template), "/{owner}/files/{id}/{path}");
+//! ```
+
+use crate::template;
+use ::utoipa::{
+    PartialSchema,
+    openapi::{
+        Object, RefOr, Required, Schema, Type,
+        path::{Parameter, ParameterBuilder, ParameterIn},
+    },
+};
+
+/// Returns a required path parameter for each param of `template`.
+///
+/// The schema of a parameter is the schema of the property of `T` with the same name, or a string
+/// if `T` has no such property.
+pub fn path_parameters<T: PartialSchema>(template: &str) -> Vec<Parameter> {
+    let schema = T::schema();
+    let properties = match &schema {
+        RefOr::T(Schema::Object(object)) => Some(&object.properties),
+        _ => None,
+    };
+    template::params(template)
+        .into_iter()
+        .map(|name| {
+            let schema = properties
+                .and_then(|properties| properties.get(name))
+                .cloned()
+                .unwrap_or_else(|| Object::with_type(Type::String).into());
+            ParameterBuilder::new()
+                .name(name)
+                .parameter_in(ParameterIn::Path)
+                .required(Required::True)
+                .schema(Some(schema))
+                .build()
+        })
+        .collect()
+}
+
+/// Converts a matchit route template to an OpenAPI path, like `/files/{*path}` to
+/// `/files/{path}`.
+pub fn path(template: &str) -> String {
+    template::openapi_path(template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::utoipa::ToSchema;
+    use serde::Deserialize;
+
+    #[allow(dead_code)]
+    #[derive(Deserialize, ToSchema)]
+    struct RepoParams {
+        owner: String,
+        #[serde(rename = "repo-id")]
+        repo_id: u64,
+    }
+
+    #[test]
+    fn test_path_parameters() {
+        let parameters = path_parameters::<RepoParams>("/{owner}/{repo-id}/{*rest}");
+        assert_eq!(
+            parameters
+                .iter()
+                .map(|parameter| parameter.name.as_str())
+                .collect::<Vec<_>>(),
+            ["owner", "repo-id", "rest"]
+        );
+        assert!(parameters.iter().all(|parameter| {
+            parameter.parameter_in == ParameterIn::Path && parameter.required == Required::True
+        }));
+
+        let schema = |index: usize| serde_json::to_value(&parameters[index].schema).unwrap();
+        assert_eq!(schema(0)["type"], "string");
+        assert_eq!(schema(1)["type"], "integer");
+        assert_eq!(schema(2)["type"], "string");
+    }
+}
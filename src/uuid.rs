@@ -0,0 +1,278 @@
+//! Restricting the textual forms of `uuid` params.
+//!
+//! [`Uuid`] params deserialize from any form `uuid` can parse without a wrapper. Use
+//! [`Formatted`] to accept only some of them and to choose the form it serializes to, for example
+//! to enforce canonical URLs.
+
+use ::uuid::Uuid;
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+use serde::{de, ser};
+
+/// The textual forms of a [`Uuid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `67e55044-10b1-426f-9247-bb680e5fe0c8`
+    Hyphenated,
+    /// `67e5504410b1426f9247bb680e5fe0c8`
+    Simple,
+    /// `{67e55044-10b1-426f-9247-bb680e5fe0c8}`
+    Braced,
+    /// `urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8`
+    Urn,
+}
+
+impl Format {
+    fn of(value: &str) -> Option<Self> {
+        match value.len() {
+            36 => Some(Self::Hyphenated),
+            32 => Some(Self::Simple),
+            38 => Some(Self::Braced),
+            45 => Some(Self::Urn),
+            _ => None,
+        }
+    }
+}
+
+/// The forms accepted and emitted by [`Formatted`].
+pub trait Formats {
+    /// The accepted forms.
+    const ACCEPT: &'static [Format];
+
+    /// The form used for serialization.
+    const EMIT: Format;
+}
+
+/// Accepts and emits only the hyphenated form.
+pub struct Hyphenated;
+
+impl Formats for Hyphenated {
+    const ACCEPT: &'static [Format] = &[Format::Hyphenated];
+    const EMIT: Format = Format::Hyphenated;
+}
+
+/// A [`Uuid`] in one of the forms of `F::ACCEPT`.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use matchit_serde::uuid::{Format, Formats, Formatted};
+///
+/// struct Lenient;
+/// impl Formats for Lenient {
+///     const ACCEPT: &'static [Format] = &[Format::Hyphenated, Format::Simple];
+///     const EMIT: Format = Format::Simple;
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Order {
+///     id: Formatted<Lenient>,
+/// }
+///
+/// let order: Order =
+///     matchit_serde::from_slice(&[("id", "67e5504410b1426f9247bb680e5fe0c8")]).unwrap();
+/// assert_eq!(*order.id, uuid::uuid!("67e55044-10b1-426f-9247-bb680e5fe0c8"));
+/// assert!(
+///     matchit_serde::from_slice::<Order>(&[("id", "{67e55044-10b1-426f-9247-bb680e5fe0c8}")])
+///         .is_err()
+/// );
+/// ```
+pub struct Formatted<F>(pub Uuid, PhantomData<F>);
+
+impl<F> Formatted<F> {
+    /// Wraps `value`, for example to serialize it in the form of `F`.
+    pub fn new(value: Uuid) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Returns the parsed value.
+    pub fn into_inner(self) -> Uuid {
+        self.0
+    }
+}
+
+impl<'de, F: Formats> de::Deserialize<'de> for Formatted<F> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FormattedVisitor(PhantomData))
+    }
+}
+
+struct FormattedVisitor<F>(PhantomData<F>);
+
+impl<F: Formats> de::Visitor<'_> for FormattedVisitor<F> {
+    type Value = Formatted<F>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a UUID in one of the forms {:?}", F::ACCEPT)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Format::of(value)
+            .filter(|format| F::ACCEPT.contains(format))
+            .and_then(|_| Uuid::try_parse(value).ok())
+            .map(Formatted::new)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+impl<F: Formats> ser::Serialize for Formatted<F> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match F::EMIT {
+            Format::Hyphenated => serializer.collect_str(&self.0.hyphenated()),
+            Format::Simple => serializer.collect_str(&self.0.simple()),
+            Format::Braced => serializer.collect_str(&self.0.braced()),
+            Format::Urn => serializer.collect_str(&self.0.urn()),
+        }
+    }
+}
+
+impl<F> Deref for Formatted<F> {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl<F> DerefMut for Formatted<F> {
+    fn deref_mut(&mut self) -> &mut Uuid {
+        &mut self.0
+    }
+}
+
+impl<F> fmt::Debug for Formatted<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Formatted").field(&self.0).finish()
+    }
+}
+
+impl<F> Clone for Formatted<F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F> Copy for Formatted<F> {}
+
+impl<F> PartialEq for Formatted<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<F> Eq for Formatted<F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Format, Formats, Formatted, Hyphenated, Uuid};
+    use crate::{
+        ParamsDeserializationError, ParamsDeserializer, from_slice, pairs::to_pairs, params,
+    };
+    use serde::{Deserialize, Serialize};
+    use uuid::uuid;
+
+    struct Urns;
+
+    impl Formats for Urns {
+        const ACCEPT: &'static [Format] = &[Format::Urn, Format::Braced];
+        const EMIT: Format = Format::Urn;
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Ids {
+        canonical: Formatted<Hyphenated>,
+        urn: Formatted<Urns>,
+    }
+
+    #[test]
+    fn test_formats() {
+        let id = uuid!("67e55044-10b1-426f-9247-bb680e5fe0c8");
+        for urn in [
+            "urn%3Auuid%3A67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "%7B67E55044-10B1-426F-9247-BB680E5FE0C8%7D",
+        ] {
+            let ids: Ids = from_slice(&[
+                ("canonical", "67e55044-10b1-426f-9247-bb680e5fe0c8"),
+                ("urn", urn),
+            ])
+            .unwrap();
+            assert_eq!(*ids.canonical, id);
+            assert_eq!(*ids.urn, id);
+            assert_eq!(
+                to_pairs(&ids).unwrap(),
+                [
+                    ("canonical", "67e55044-10b1-426f-9247-bb680e5fe0c8"),
+                    ("urn", "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"),
+                ]
+                .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            );
+        }
+
+        for canonical in [
+            "67e5504410b1426f9247bb680e5fe0c8",
+            "{67e55044-10b1-426f-9247-bb680e5fe0c8}",
+            "67e55044-10b1-426f-9247-bb680e5fe0cx",
+        ] {
+            let err = from_slice::<Ids>(&[
+                ("canonical", canonical),
+                ("urn", "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            ])
+            .unwrap_err();
+            assert!(matches!(
+                err,
+                ParamsDeserializationError::ParseErrorAtKey { ref key, .. } if key == "canonical"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_plain_uuid() {
+        #[derive(Debug, Deserialize)]
+        struct Item {
+            id: Uuid,
+        }
+
+        let id = uuid!("67e55044-10b1-426f-9247-bb680e5fe0c8");
+        let item: Item =
+            ParamsDeserializer::new(params! { "id" => "67e5504410b1426f9247bb680e5fe0c8" })
+                .deserialize()
+                .unwrap();
+        assert_eq!(item.id, id);
+        let single: Uuid =
+            ParamsDeserializer::new(params! { "id" => "67e55044-10b1-426f-9247-bb680e5fe0c8" })
+                .deserialize()
+                .unwrap();
+        assert_eq!(single, id);
+
+        let err = ParamsDeserializer::new(params! { "id" => "67e55044" })
+            .deserialize::<Item>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsDeserializationError::ParseErrorAtKey { ref key, expected_type: "uuid::Uuid", .. } if key == "id"
+        ));
+
+        #[derive(Serialize)]
+        struct Defaults {
+            id: Uuid,
+        }
+        assert_eq!(
+            to_pairs(&Defaults { id }).unwrap(),
+            [(
+                "id".to_owned(),
+                "67e55044-10b1-426f-9247-bb680e5fe0c8".to_owned()
+            )]
+        );
+    }
+}
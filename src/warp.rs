@@ -0,0 +1,120 @@
+//! A warp filter matching matchit templates.
+
+use crate::{ParamsBuf, ParamsDeserializationError};
+use alloc::{string::String, sync::Arc};
+use serde::de::DeserializeOwned;
+use warp::{
+    Filter, Rejection,
+    filters::path::{FullPath, full},
+    reject::{self, Reject},
+};
+
+/// Matches `template` against the full request path and extracts the params into `T`.
+///
+/// A field of `T` renamed to [`TEMPLATE`](crate::TEMPLATE) gets `template`, and one renamed to
+/// [`RAW_PATH`](crate::RAW_PATH) the request path.
+///
+/// Rejects with [`reject::not_found`] if the path doesn't match, and with the
+/// [`ParamsDeserializationError`] as custom rejection if the params can't be decoded or
+/// deserialized.
+///
+/// # Panics
+///
+/// Panics if `template` isn't a valid matchit route.
+///
+/// ```
+/// # use serde::Deserialize;
+/// use warp::Filter;
+///
+/// #[derive(Deserialize)]
+/// struct UserPost {
+///     user: String,
+///     post: u32,
+/// }
+///
+/// let route = matchit_serde::warp::params::<UserPost>("/users/{user}/posts/{post}")
+///     .map(|params: UserPost| format!("post {} by {}", params.post, params.user));
+/// ```
+pub fn params<T>(template: &str) -> impl Filter<Extract = (T,), Error = Rejection> + Clone
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let mut router = matchit::Router::new();
+    router
+        .insert(template, ())
+        .unwrap_or_else(|err| panic!("invalid template {template:?}: {err}"));
+    let router = Arc::new((router, String::from(template)));
+    full().and_then(move |path: FullPath| {
+        let (router, template) = &*router;
+        core::future::ready(extract(router, template, path.as_str()))
+    })
+}
+
+fn extract<T>(router: &matchit::Router<()>, template: &str, path: &str) -> Result<T, Rejection>
+where
+    T: DeserializeOwned,
+{
+    let matched = router.at(path).map_err(|_| reject::not_found())?;
+    ParamsBuf::try_from(&matched.params)
+        .and_then(|params| {
+            params
+                .with_raw_path(path)
+                .deserializer()
+                .deserialize_route(template)
+        })
+        .map_err(reject::custom)
+}
+
+impl Reject for ParamsDeserializationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::extract;
+    use crate::ParamsDeserializationError;
+    use serde::Deserialize;
+    use std::string::String;
+
+    #[test]
+    fn test_extract() {
+        let mut router = matchit::Router::new();
+        router.insert("/users/{id}", ()).unwrap();
+
+        let template = "/users/{id}";
+
+        assert_eq!(
+            extract::<(u32,)>(&router, template, "/users/7").unwrap(),
+            (7,)
+        );
+        assert!(
+            extract::<(u32,)>(&router, template, "/posts/7")
+                .unwrap_err()
+                .is_not_found()
+        );
+        let rejection = extract::<(u32,)>(&router, template, "/users/x").unwrap_err();
+        assert!(matches!(
+            rejection.find::<ParamsDeserializationError>(),
+            Some(ParamsDeserializationError::ParseErrorAtIndex { .. })
+        ));
+    }
+
+    #[test]
+    fn test_extract_template() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct User {
+            id: u32,
+            #[serde(rename = "$template")]
+            template: String,
+        }
+
+        let mut router = matchit::Router::new();
+        router.insert("/users/{id}", ()).unwrap();
+
+        assert_eq!(
+            extract::<User>(&router, "/users/{id}", "/users/7").unwrap(),
+            User {
+                id: 7,
+                template: "/users/{id}".into(),
+            }
+        );
+    }
+}
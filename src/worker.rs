@@ -0,0 +1,81 @@
+use crate::{Decoding, Params, ParamsDeserializationError, ParamsDeserializer, reserved};
+use alloc::{borrow::Cow, vec::Vec};
+use core::str::Utf8Error;
+use serde::de;
+
+/// Deserializes `T` from the route params of a Cloudflare Workers [`RouteContext`].
+///
+/// The router of the `worker` crate only allows looking up params by key, so `T` has to be a
+/// struct. Its fields are looked up by name and percent-decoded like params from
+/// [`matchit::Params`].
+///
+/// [`RouteContext`]: ::worker::RouteContext
+pub fn from_route_context<'de, T, D>(
+    ctx: &'de ::worker::RouteContext<D>,
+) -> Result<T, ParamsDeserializationError>
+where
+    T: de::Deserialize<'de>,
+{
+    from_lookup(|key| ctx.param(key).map(|v| v.as_str()))
+}
+
+/// Looks up the fields of the struct `T` by name and deserializes it from the found params.
+fn from_lookup<'de, T>(
+    lookup: impl Fn(&str) -> Option<&'de str>,
+) -> Result<T, ParamsDeserializationError>
+where
+    T: de::Deserialize<'de>,
+{
+    let fields =
+        reserved::fields::<T>().ok_or_else(ParamsDeserializationError::unsupported_type::<T>)?;
+    let params = fields
+        .iter()
+        .filter_map(|&key| lookup(key).map(|value| (key, value)))
+        .map(|(key, value)| Ok((Cow::Borrowed(key), Decoding::Percent.decode(value)?)))
+        .collect::<Result<Vec<_>, Utf8Error>>()?;
+    ParamsDeserializer::new(Params(params)).deserialize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_lookup;
+    use crate::ParamsDeserializationError;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Post {
+        user: String,
+        id: u32,
+        draft: Option<bool>,
+    }
+
+    #[test]
+    fn test_lookup_struct() {
+        let map = HashMap::from([("user", "a%20b".to_owned()), ("id", "7".to_owned())]);
+        assert_eq!(
+            from_lookup::<Post>(|key| map.get(key).map(|v| v.as_str())).unwrap(),
+            Post {
+                user: "a b".to_owned(),
+                id: 7,
+                draft: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_lookup_parse_error() {
+        let map = HashMap::from([("user", "a".to_owned()), ("id", "x".to_owned())]);
+        let err = from_lookup::<Post>(|key| map.get(key).map(|v| v.as_str())).unwrap_err();
+        assert_eq!(err.kind(), "parse_error_at_key");
+        assert_eq!(err.key(), Some("id"));
+    }
+
+    #[test]
+    fn test_lookup_non_struct() {
+        assert!(matches!(
+            from_lookup::<u32>(|_| Some("1")),
+            Err(ParamsDeserializationError::UnsupportedType(_))
+        ));
+    }
+}